@@ -0,0 +1,384 @@
+// Off-chain helpers for Rust bots and backends, builds instructions from the
+// Anchor-generated `accounts`/`instruction` modules so nobody hand-rolls Borsh layouts
+use anchor_lang::{
+    AccountDeserialize, AnchorSerialize, Discriminator, InstructionData, ToAccountMetas,
+};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::system_program;
+
+use crate::{Category, Config, Priority, Task, TaskIndex, UserProfile, ID};
+
+#[derive(Debug)]
+pub enum ClientError {
+    Rpc(solana_client::client_error::ClientError),
+    Deserialize(anchor_lang::error::Error),
+}
+
+impl From<solana_client::client_error::ClientError> for ClientError {
+    fn from(err: solana_client::client_error::ClientError) -> Self {
+        ClientError::Rpc(err)
+    }
+}
+
+impl From<anchor_lang::error::Error> for ClientError {
+    fn from(err: anchor_lang::error::Error) -> Self {
+        ClientError::Deserialize(err)
+    }
+}
+
+impl std::fmt::Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::Rpc(err) => write!(f, "rpc error: {}", err),
+            ClientError::Deserialize(err) => write!(f, "deserialize error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+pub type ClientResult<T> = std::result::Result<T, ClientError>;
+
+// PDAs
+
+pub fn profile_pda(author: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"profile", author.as_ref()], &ID).0
+}
+
+pub fn task_pda(author: &Pubkey, task_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"task", author.as_ref(), task_id.to_le_bytes().as_ref()],
+        &ID,
+    )
+    .0
+}
+
+pub fn task_index_pda(author: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"task_index", author.as_ref()], &ID).0
+}
+
+pub fn config_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"config"], &ID).0
+}
+
+pub fn global_stats_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"global_stats"], &ID).0
+}
+
+pub fn treasury_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"treasury"], &ID).0
+}
+
+pub fn activity_log_pda(board: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"activity", board.as_ref()], &ID).0
+}
+
+pub fn subscription_pda(author: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"subscription", author.as_ref()], &ID).0
+}
+
+pub fn stake_pda(task: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"stake", task.as_ref()], &ID).0
+}
+
+pub fn escrow_pda(task: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"escrow", task.as_ref()], &ID).0
+}
+
+// Signs the self-CPI that records `emit_cpi!` events
+pub fn event_authority_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"__event_authority"], &ID).0
+}
+
+// Instructions
+
+pub fn init_profile_ix(author: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: crate::accounts::InitProfile {
+            author: *author,
+            profile: profile_pda(author),
+            task_index: task_index_pda(author),
+            global_stats: global_stats_pda(),
+            system_program: system_program::ID,
+            config: config_pda(),
+        }
+        .to_account_metas(None),
+        data: crate::instruction::InitProfile {}.data(),
+    }
+}
+
+// `task_id` is the profile's current `task_count`, fetch the profile first. `payer` covers
+// rent and fees, pass the author again unless a relayer co-signs
+#[allow(clippy::too_many_arguments)]
+pub fn create_task_ix(
+    payer: &Pubkey,
+    author: &Pubkey,
+    task_id: u64,
+    title: String,
+    description: String,
+    due_date: Option<i64>,
+    priority: Priority,
+    estimate_seconds: Option<u64>,
+    board: Option<Pubkey>,
+) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: crate::accounts::CreateTask {
+            payer: *payer,
+            author: *author,
+            config: config_pda(),
+            profile: profile_pda(author),
+            // Subscribers swap in `subscription_pda(author)` to get their tier's limit
+            subscription: None,
+            task: task_pda(author, task_id),
+            task_index: Some(task_index_pda(author)),
+            treasury: treasury_pda(),
+            global_stats: global_stats_pda(),
+            board,
+            activity_log: board.as_ref().map(activity_log_pda),
+            system_program: system_program::ID,
+            event_authority: event_authority_pda(),
+            program: ID,
+        }
+        .to_account_metas(None),
+        data: crate::instruction::CreateTask {
+            title,
+            description,
+            due_date,
+            priority,
+            estimate_seconds,
+        }
+        .data(),
+    }
+}
+
+// Same as `create_task_ix` but safe to resend, keep the `task_id` of the first attempt
+#[allow(clippy::too_many_arguments)]
+pub fn create_task_if_needed_ix(
+    payer: &Pubkey,
+    author: &Pubkey,
+    task_id: u64,
+    title: String,
+    description: String,
+    due_date: Option<i64>,
+    priority: Priority,
+    estimate_seconds: Option<u64>,
+    board: Option<Pubkey>,
+) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: crate::accounts::CreateTaskIfNeeded {
+            payer: *payer,
+            author: *author,
+            config: config_pda(),
+            profile: profile_pda(author),
+            subscription: None,
+            task: task_pda(author, task_id),
+            task_index: Some(task_index_pda(author)),
+            treasury: treasury_pda(),
+            global_stats: global_stats_pda(),
+            board,
+            activity_log: board.as_ref().map(activity_log_pda),
+            system_program: system_program::ID,
+            event_authority: event_authority_pda(),
+            program: ID,
+        }
+        .to_account_metas(None),
+        data: crate::instruction::CreateTaskIfNeeded {
+            task_id,
+            title,
+            description,
+            due_date,
+            priority,
+            estimate_seconds,
+        }
+        .data(),
+    }
+}
+
+pub fn update_task_ix(
+    author: &Pubkey,
+    task_id: u64,
+    description: String,
+    due_date: Option<i64>,
+    board: Option<Pubkey>,
+) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: crate::accounts::UpdateTask {
+            author: *author,
+            config: config_pda(),
+            task: task_pda(author, task_id),
+            delegate: None,
+            board,
+            activity_log: board.as_ref().map(activity_log_pda),
+            revision: None,
+            system_program: system_program::ID,
+            event_authority: event_authority_pda(),
+            program: ID,
+        }
+        .to_account_metas(None),
+        data: crate::instruction::UpdateTask {
+            description,
+            due_date,
+        }
+        .data(),
+    }
+}
+
+// Dependencies and subtasks still have to be appended as remaining accounts
+pub fn complete_task_ix(
+    signer: &Pubkey,
+    task: &Task,
+    board: Option<Pubkey>,
+    memo: Option<String>,
+) -> Instruction {
+    let task_key = task_pda(&task.author, task.task_id);
+    let (stake, author) = if task.has_stake {
+        (Some(stake_pda(&task_key)), Some(task.author))
+    } else {
+        (None, None)
+    };
+    Instruction {
+        program_id: ID,
+        accounts: crate::accounts::CompleteTask {
+            signer: *signer,
+            task: task_key,
+            delegate: None,
+            global_stats: global_stats_pda(),
+            profile: profile_pda(&task.author),
+            board,
+            activity_log: board.as_ref().map(activity_log_pda),
+            milestone: task.milestone,
+            stake,
+            author,
+            memo_program: memo.as_ref().map(|_| anchor_spl::memo::ID),
+            // Board reward pools pass their pool and the worker's tracker here
+            reward_pool: None,
+            reward_tracker: None,
+            config: config_pda(),
+            event_authority: event_authority_pda(),
+            program: ID,
+        }
+        .to_account_metas(None),
+        data: crate::instruction::CompleteTask { memo }.data(),
+    }
+}
+
+// `escrow_funder` is the fetched `Escrow`'s funder, the refund goes there when
+// `task.has_bounty`. The parent and dependencies are appended for their `dependent_count`
+pub fn delete_task_ix(signer: &Pubkey, task: &Task, escrow_funder: Option<Pubkey>) -> Instruction {
+    let task_key = task_pda(&task.author, task.task_id);
+    let escrow = task.has_bounty.then(|| escrow_pda(&task_key));
+    let mut accounts = crate::accounts::DeleteTask {
+        author: *signer,
+        task: task_key,
+        task_index: Some(task_index_pda(&task.author)),
+        profile: profile_pda(&task.author),
+        board: task.board,
+        activity_log: task.board.as_ref().map(activity_log_pda),
+        escrow,
+        funder: escrow.and(escrow_funder),
+        config: config_pda(),
+        event_authority: event_authority_pda(),
+        program: ID,
+    }
+    .to_account_metas(None);
+    accounts.extend(
+        task.parent
+            .iter()
+            .chain(task.blocked_by.iter())
+            .map(|dependency| AccountMeta::new(*dependency, false)),
+    );
+    Instruction {
+        program_id: ID,
+        accounts,
+        data: crate::instruction::DeleteTask {}.data(),
+    }
+}
+
+// Fetching
+
+fn fetch<T: AccountDeserialize>(rpc: &RpcClient, address: &Pubkey) -> ClientResult<T> {
+    let data = rpc.get_account_data(address)?;
+    Ok(T::try_deserialize(&mut data.as_slice())?)
+}
+
+pub fn fetch_task(rpc: &RpcClient, address: &Pubkey) -> ClientResult<Task> {
+    fetch(rpc, address)
+}
+
+pub fn fetch_profile(rpc: &RpcClient, author: &Pubkey) -> ClientResult<UserProfile> {
+    fetch(rpc, &profile_pda(author))
+}
+
+// Ids in creation order, page through them and derive each address with `task_pda`
+pub fn fetch_task_index(rpc: &RpcClient, author: &Pubkey) -> ClientResult<TaskIndex> {
+    fetch(rpc, &task_index_pda(author))
+}
+
+pub fn fetch_config(rpc: &RpcClient) -> ClientResult<Config> {
+    fetch(rpc, &config_pda())
+}
+
+// Every task of an author, filtered on the discriminator and the author right after it
+pub fn fetch_tasks_by_author(
+    rpc: &RpcClient,
+    author: &Pubkey,
+) -> ClientResult<Vec<(Pubkey, Task)>> {
+    fetch_tasks(rpc, author, Vec::new())
+}
+
+pub fn fetch_tasks_by_category(
+    rpc: &RpcClient,
+    author: &Pubkey,
+    category: Category,
+) -> ClientResult<Vec<(Pubkey, Task)>> {
+    let encoded = category.try_to_vec().expect("category always serializes");
+    fetch_tasks(
+        rpc,
+        author,
+        vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            Task::CATEGORY_OFFSET,
+            &encoded,
+        ))],
+    )
+}
+
+fn fetch_tasks(
+    rpc: &RpcClient,
+    author: &Pubkey,
+    extra_filters: Vec<RpcFilterType>,
+) -> ClientResult<Vec<(Pubkey, Task)>> {
+    let mut filters = vec![
+        RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, &Task::DISCRIMINATOR)),
+        RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            Task::AUTHOR_OFFSET,
+            author.as_ref(),
+        )),
+    ];
+    filters.extend(extra_filters);
+    let config = RpcProgramAccountsConfig {
+        filters: Some(filters),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+    rpc.get_program_accounts_with_config(&ID, config)?
+        .into_iter()
+        .map(|(address, account)| {
+            Ok((
+                address,
+                Task::try_deserialize(&mut account.data.as_slice())?,
+            ))
+        })
+        .collect()
+}
@@ -1,34 +1,215 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::system_program;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::memo::{self, BuildMemo, Memo};
+use anchor_spl::metadata::mpl_token_metadata::types::DataV2;
+use anchor_spl::metadata::{create_metadata_accounts_v3, CreateMetadataAccountsV3, Metadata};
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFeeConfig;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_interface::{self, TokenInterface};
+use mpl_bubblegum::instructions::MintV1CpiBuilder;
+use mpl_bubblegum::types::{MetadataArgs, TokenProgramVersion, TokenStandard};
+use pyth_solana_receiver_sdk::price_update::{get_feed_id_from_hex, PriceUpdateV2};
+use spl_account_compression::cpi::accounts::{Initialize as InitTree, Modify as ModifyTree};
+use spl_account_compression::{program::SplAccountCompression, Noop};
+use switchboard_on_demand::PullFeedAccountData;
 
 // This is your program's public key and it will update
 // automatically when you build the project.
 declare_id!("8rwZJ58gyv2yY2eUanMYVWohBBLeSAguNDo736k2nDJf");
 
+#[cfg(all(feature = "client", not(target_os = "solana")))]
+pub mod client;
+
 #[program]
 pub mod task_manager {
     use super::*;
 
+    // Creating the profile that hands out task ids for an author
+    pub fn init_profile(ctx: Context<InitProfile>) -> Result<()> {
+        let profile = &mut ctx.accounts.profile;
+        profile.author = ctx.accounts.author.key();
+        profile.task_count = 0;
+        profile.board_count = 0;
+        profile.recurring_count = 0;
+        profile.tasks_created = 0;
+        profile.tasks_completed = 0;
+        profile.tasks_deleted = 0;
+        profile.open_count = 0;
+        profile.template_count = 0;
+        profile.pinned_count = 0;
+        profile.milestone_count = 0;
+        profile.sprint_count = 0;
+        profile.active_timer = None;
+        profile.pomodoros_today = 0;
+        profile.pomodoro_day = 0;
+        ctx.accounts.task_index.author = profile.author;
+        ctx.accounts.task_index.task_ids = Vec::new();
+        profile.current_streak = 0;
+        profile.longest_streak = 0;
+        profile.last_completion_day = 0;
+        profile.xp = 0;
+        profile.level = 0;
+        let global_stats = &mut ctx.accounts.global_stats;
+        global_stats.unique_authors = global_stats.unique_authors.saturating_add(1);
+        msg!("Profile created, Author: {}", profile.author);
+        Ok(())
+    }
+
+    // Creating the program-wide stats singleton, anyone can do this once
+    pub fn init_global_stats(ctx: Context<InitGlobalStats>) -> Result<()> {
+        let global_stats = &mut ctx.accounts.global_stats;
+        global_stats.total_tasks = 0;
+        global_stats.total_completed = 0;
+        global_stats.unique_authors = 0;
+        msg!("Global stats created");
+        Ok(())
+    }
+
+    // Creating the config, only the program's upgrade authority can do this
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        max_title_len: u32,
+        max_description_len: u32,
+        max_tasks_per_user: u64,
+        creation_fee: u64,
+    ) -> Result<()> {
+        validate_limits(max_title_len, max_description_len)?;
+
+        let config = &mut ctx.accounts.config;
+        config.admin = ctx.accounts.admin.key();
+        config.max_title_len = max_title_len;
+        config.max_description_len = max_description_len;
+        config.max_tasks_per_user = max_tasks_per_user;
+        config.creation_fee = creation_fee;
+        config.paused = false;
+        config.expiry_grace = 0;
+        config.expiry_tip = 0;
+        config.badge_tree = None;
+        config.max_snoozes = 0;
+        config.allowed_mints = Vec::new();
+        config.tier_prices = [0; 2];
+        config.tier_task_limits = [0; 2];
+        config.time_oracle = None;
+        config.arbiter = None;
+        msg!(
+            "Config created, Admin: {}, Title: {}, Description: {}, Tasks per user: {}, Fee: {}",
+            config.admin,
+            config.max_title_len,
+            config.max_description_len,
+            config.max_tasks_per_user,
+            config.creation_fee
+        );
+        Ok(())
+    }
+
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        max_title_len: u32,
+        max_description_len: u32,
+        max_tasks_per_user: u64,
+        creation_fee: u64,
+    ) -> Result<()> {
+        validate_limits(max_title_len, max_description_len)?;
+
+        let config = &mut ctx.accounts.config;
+        config.max_title_len = max_title_len;
+        config.max_description_len = max_description_len;
+        config.max_tasks_per_user = max_tasks_per_user;
+        config.creation_fee = creation_fee;
+        msg!(
+            "Config updated, Title: {}, Description: {}, Tasks per user: {}, Fee: {}",
+            config.max_title_len,
+            config.max_description_len,
+            config.max_tasks_per_user,
+            config.creation_fee
+        );
+        Ok(())
+    }
+
     // Creating the task
-    pub fn create_task(ctx: Context<CreateTask>, title: String, description: String) -> Result<()> {
+    pub fn create_task(
+        ctx: Context<CreateTask>,
+        title: String,
+        description: String,
+        due_date: Option<i64>,
+        priority: Priority,
+        estimate_seconds: Option<u64>,
+    ) -> Result<()> {
         let task = &mut ctx.accounts.task;
+        let profile = &mut ctx.accounts.profile;
         let clock = Clock::get()?;
 
-        require!(title.len() <= 100, ErrorTask::TitleTooLong);
-        require!(!title.trim().is_empty(), ErrorTask::TitleIsEmpty);
-        require!(description.len() <= 1000, ErrorTask::DescriptionTooLong);
-        require!(
-            !description.trim().is_empty(),
-            ErrorTask::DescriptionIsEmpty
+        validate_title(&title, &ctx.accounts.config)?;
+        validate_description(&description, false, &ctx.accounts.config)?;
+        validate_due_date(due_date, clock.unix_timestamp)?;
+        validate_estimate(estimate_seconds)?;
+        collect_creation_fee(
+            &ctx.accounts.config,
+            &ctx.accounts.payer,
+            &ctx.accounts.treasury,
+            &ctx.accounts.system_program,
+        )?;
+
+        task.init(
+            ctx.accounts.author.key(),
+            profile.task_count,
+            title,
+            description,
+            due_date,
+            priority,
+            clock.unix_timestamp,
         );
 
-        task.author = ctx.accounts.author.key();
-        task.title = title.clone();
-        task.description = description.clone();
-        task.is_completed = false;
-        task.created_at = clock.unix_timestamp;
+        task.bump = ctx.bumps.task;
+        task.estimate_seconds = estimate_seconds;
+        index_task(
+            ctx.accounts.task_index.as_mut(),
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            task.task_id,
+        )?;
+        profile.task_count = profile
+            .task_count
+            .checked_add(1)
+            .ok_or(ErrorTask::TaskCountOverflow)?;
+        require_open_slot(
+            profile,
+            &ctx.accounts.config,
+            ctx.accounts.subscription.as_ref(),
+        )?;
+        profile.record_created();
+        ctx.accounts.global_stats.record_created();
+        if let Some(board) = ctx.accounts.board.as_mut() {
+            task.board = Some(board.key());
+            task.visibility = Visibility::BoardOnly;
+            board.task_count = board
+                .task_count
+                .checked_add(1)
+                .ok_or(ErrorTask::TaskCountOverflow)?;
+        }
+        log_activity(
+            ctx.accounts.activity_log.as_mut(),
+            task.board,
+            task.author,
+            Activity::Created,
+            task.key(),
+        )?;
 
+        emit_cpi!(TaskCreated {
+            task: task.key(),
+            task_id: task.task_id,
+            author: task.author,
+            created_at: task.created_at,
+        });
         msg!(
-            "Task create, Title: {}, Author: {}, Created at: {}",
+            "Task create, Id: {}, Title: {}, Author: {}, Created at: {}",
+            task.task_id,
             task.title,
             task.author,
             task.created_at
@@ -37,17 +218,225 @@ pub mod task_manager {
         Ok(())
     }
 
-    // Updating the description in the task
-    pub fn update_task(ctx: Context<UpdateTask>, description: String) -> Result<()> {
+    // Retry-safe `create_task`: the caller fixes `task_id` to the profile's `task_count` once
+    // and resends as often as it likes, a task already sitting there with the same title is
+    // taken as the earlier attempt having landed
+    pub fn create_task_if_needed(
+        ctx: Context<CreateTaskIfNeeded>,
+        task_id: u64,
+        title: String,
+        description: String,
+        due_date: Option<i64>,
+        priority: Priority,
+        estimate_seconds: Option<u64>,
+    ) -> Result<()> {
         let task = &mut ctx.accounts.task;
+        let profile = &mut ctx.accounts.profile;
+        let clock = Clock::get()?;
 
-        require!(description.len() <= 1000, ErrorTask::DescriptionTooLong);
-        require!(
-            !description.trim().is_empty(),
-            ErrorTask::DescriptionIsEmpty
+        if task.author != Pubkey::default() {
+            require!(task.title == title, ErrorTask::TaskIdTaken);
+            msg!(
+                "Task already exists, Id: {}, Title: {}, Author: {}",
+                task.task_id,
+                task.title,
+                task.author
+            );
+            return Ok(());
+        }
+        require!(task_id == profile.task_count, ErrorTask::TaskIdTaken);
+
+        validate_title(&title, &ctx.accounts.config)?;
+        validate_description(&description, false, &ctx.accounts.config)?;
+        validate_due_date(due_date, clock.unix_timestamp)?;
+        validate_estimate(estimate_seconds)?;
+        collect_creation_fee(
+            &ctx.accounts.config,
+            &ctx.accounts.payer,
+            &ctx.accounts.treasury,
+            &ctx.accounts.system_program,
+        )?;
+
+        task.init(
+            ctx.accounts.author.key(),
+            profile.task_count,
+            title,
+            description,
+            due_date,
+            priority,
+            clock.unix_timestamp,
+        );
+
+        task.bump = ctx.bumps.task;
+        task.estimate_seconds = estimate_seconds;
+        index_task(
+            ctx.accounts.task_index.as_mut(),
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            task.task_id,
+        )?;
+        profile.task_count = profile
+            .task_count
+            .checked_add(1)
+            .ok_or(ErrorTask::TaskCountOverflow)?;
+        require_open_slot(
+            profile,
+            &ctx.accounts.config,
+            ctx.accounts.subscription.as_ref(),
+        )?;
+        profile.record_created();
+        ctx.accounts.global_stats.record_created();
+        if let Some(board) = ctx.accounts.board.as_mut() {
+            task.board = Some(board.key());
+            task.visibility = Visibility::BoardOnly;
+            board.task_count = board
+                .task_count
+                .checked_add(1)
+                .ok_or(ErrorTask::TaskCountOverflow)?;
+        }
+        log_activity(
+            ctx.accounts.activity_log.as_mut(),
+            task.board,
+            task.author,
+            Activity::Created,
+            task.key(),
+        )?;
+
+        emit_cpi!(TaskCreated {
+            task: task.key(),
+            task_id: task.task_id,
+            author: task.author,
+            created_at: task.created_at,
+        });
+        msg!(
+            "Task create, Id: {}, Title: {}, Author: {}, Created at: {}",
+            task.task_id,
+            task.title,
+            task.author,
+            task.created_at
+        );
+
+        Ok(())
+    }
+
+    // Creating a subtask under one of the author's tasks
+    pub fn create_subtask(
+        ctx: Context<CreateSubtask>,
+        title: String,
+        description: String,
+        due_date: Option<i64>,
+        priority: Priority,
+    ) -> Result<()> {
+        let parent_key = ctx.accounts.parent.key();
+        let task = &mut ctx.accounts.task;
+        let profile = &mut ctx.accounts.profile;
+        let clock = Clock::get()?;
+
+        validate_title(&title, &ctx.accounts.config)?;
+        validate_description(&description, false, &ctx.accounts.config)?;
+        validate_due_date(due_date, clock.unix_timestamp)?;
+        collect_creation_fee(
+            &ctx.accounts.config,
+            &ctx.accounts.author,
+            &ctx.accounts.treasury,
+            &ctx.accounts.system_program,
+        )?;
+
+        task.init(
+            ctx.accounts.author.key(),
+            profile.task_count,
+            title,
+            description,
+            due_date,
+            priority,
+            clock.unix_timestamp,
+        );
+
+        task.bump = ctx.bumps.task;
+        index_task(
+            ctx.accounts.task_index.as_mut(),
+            &ctx.accounts.author,
+            &ctx.accounts.system_program,
+            task.task_id,
+        )?;
+        task.parent = Some(parent_key);
+        let parent = &mut ctx.accounts.parent;
+        parent.dependent_count = parent.dependent_count.saturating_add(1);
+        profile.task_count = profile
+            .task_count
+            .checked_add(1)
+            .ok_or(ErrorTask::TaskCountOverflow)?;
+        require_open_slot(
+            profile,
+            &ctx.accounts.config,
+            ctx.accounts.subscription.as_ref(),
+        )?;
+        profile.record_created();
+        ctx.accounts.global_stats.record_created();
+
+        emit_cpi!(TaskCreated {
+            task: task.key(),
+            task_id: task.task_id,
+            author: task.author,
+            created_at: task.created_at,
+        });
+        msg!(
+            "Subtask create, Id: {}, Title: {}, Parent: {}, Author: {}",
+            task.task_id,
+            task.title,
+            parent_key,
+            task.author
         );
 
+        Ok(())
+    }
+
+    // Updating the description and due date in the task, a `None` due date clears it
+    pub fn update_task(
+        ctx: Context<UpdateTask>,
+        description: String,
+        due_date: Option<i64>,
+    ) -> Result<()> {
+        // A session key acts for the task's authority
+        let mut editor = ctx.accounts.author.key();
+        if ctx.accounts.task.authority != editor {
+            require_delegate(ctx.accounts.delegate.as_ref(), Delegate::UPDATE)?;
+            editor = ctx.accounts.task.authority;
+        }
+        require_board_editor(&ctx.accounts.task, ctx.accounts.board.as_ref(), &editor)?;
+        let task = &mut ctx.accounts.task;
+        let clock = Clock::get()?;
+
+        // Done tasks have to be reopened before they can be edited
+        require!(!task.is_completed(), ErrorTask::AlreadyCompleted);
+        require!(task.content_hash.is_none(), ErrorTask::ContentIsHashed);
+        validate_description(&description, task.is_private, &ctx.accounts.config)?;
+        validate_due_date(due_date, clock.unix_timestamp)?;
+
+        if let Some(revision) = ctx.accounts.revision.as_mut() {
+            revision.task = task.key();
+            revision.revision_id = task.revision_count;
+            revision.previous_description_hash = hash(task.description.as_bytes()).to_bytes();
+            revision.editor = ctx.accounts.author.key();
+            revision.edited_at = clock.unix_timestamp;
+            task.revision_count = task.revision_count.saturating_add(1);
+        }
         task.description = description.clone();
+        task.due_date = due_date;
+        task.updated_at = clock.unix_timestamp;
+        log_activity(
+            ctx.accounts.activity_log.as_mut(),
+            task.board,
+            ctx.accounts.author.key(),
+            Activity::Updated,
+            task.key(),
+        )?;
+        emit_cpi!(TaskUpdated {
+            task: task.key(),
+            task_id: task.task_id,
+            author: task.author,
+            updated_at: task.updated_at,
+        });
         msg!(
             "Task description updated, Title: {}, Author: {}",
             task.title,
@@ -57,9 +446,145 @@ pub mod task_manager {
         Ok(())
     }
 
-    pub fn complete_task(ctx: Context<CompleteTask>) -> Result<()> {
+    // Renaming the task, the title is no longer part of the seeds so the PDA stays put
+    pub fn rename_task(ctx: Context<RenameTask>, title: String) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
         let task = &mut ctx.accounts.task;
-        task.is_completed = true;
+        let clock = Clock::get()?;
+
+        validate_title(&title, &ctx.accounts.config)?;
+
+        msg!(
+            "Task renamed, Old title: {}, New title: {}, Author: {}",
+            task.title,
+            title,
+            task.author
+        );
+        task.title = title;
+        task.updated_at = clock.unix_timestamp;
+        emit_cpi!(TaskUpdated {
+            task: task.key(),
+            task_id: task.task_id,
+            author: task.author,
+            updated_at: task.updated_at,
+        });
+
+        Ok(())
+    }
+
+    // Completing the task, every dependency and every subtask passed in remaining_accounts
+    // must already be done, and all dependencies have to be passed
+    // `memo` is written through the SPL Memo program so explorers show it on the transaction
+    pub fn complete_task<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CompleteTask<'info>>,
+        memo: Option<String>,
+    ) -> Result<()> {
+        let events = EventAuthority {
+            info: ctx.accounts.event_authority.to_account_info(),
+            bump: ctx.bumps.event_authority,
+        };
+        // A session key acts for the task's authority
+        let mut editor = ctx.accounts.signer.key();
+        if !ctx.accounts.task.can_complete(&editor) {
+            require_delegate(ctx.accounts.delegate.as_ref(), Delegate::COMPLETE)?;
+            editor = ctx.accounts.task.authority;
+        }
+        require_board_editor(&ctx.accounts.task, ctx.accounts.board.as_ref(), &editor)?;
+        require!(
+            !ctx.accounts.task.is_completed(),
+            ErrorTask::AlreadyCompleted
+        );
+        require!(
+            ctx.accounts.task.status.can_transition_to(TaskStatus::Done),
+            ErrorTask::InvalidStatusTransition
+        );
+        require!(
+            ctx.accounts.task.status != TaskStatus::PendingReview,
+            ErrorTask::AwaitingReview
+        );
+        require!(
+            ctx.accounts.task.required_approvals == 0,
+            ErrorTask::ApprovalsRequired
+        );
+        require_dependencies_done(&ctx.accounts.task, ctx.remaining_accounts)?;
+        if let Some(memo) = memo {
+            require!(memo.len() <= 200, ErrorTask::MemoTooLong);
+            let memo_program = ctx
+                .accounts
+                .memo_program
+                .as_ref()
+                .ok_or(ErrorTask::MemoProgramMissing)?;
+            memo::build_memo(
+                CpiContext::new(memo_program.to_account_info(), BuildMemo {}),
+                memo.as_bytes(),
+            )?;
+        }
+
+        let clock = Clock::get()?;
+        // Reviewed tasks wait for `approve_completion`, nothing is paid out or counted yet
+        if ctx.accounts.task.reviewer.is_some() {
+            let task = &mut ctx.accounts.task;
+            task.status = TaskStatus::PendingReview;
+            task.updated_at = clock.unix_timestamp;
+            log_activity(
+                ctx.accounts.activity_log.as_mut(),
+                task.board,
+                ctx.accounts.signer.key(),
+                Activity::StatusChanged,
+                task.key(),
+            )?;
+            notify(
+                &events,
+                task.reviewer.unwrap_or_default(),
+                NotificationKind::ReviewRequested,
+                task,
+            )?;
+            emit_watched_status(&events, task)?;
+            emit_cpi!(TaskSubmittedForReview {
+                task: task.key(),
+                task_id: task.task_id,
+                submitted_by: ctx.accounts.signer.key(),
+                reviewer: task.reviewer.unwrap_or_default(),
+                timestamp: clock.unix_timestamp,
+            });
+            msg!(
+                "Task submitted for review. Title: {}, Author: {}",
+                task.title,
+                task.author
+            );
+            return Ok(());
+        }
+        finish_completion(
+            &events,
+            &mut ctx.accounts.task,
+            &mut ctx.accounts.profile,
+            &mut ctx.accounts.global_stats,
+            ctx.accounts.milestone.as_mut(),
+            ctx.accounts.stake.as_ref(),
+            ctx.accounts.author.as_ref(),
+            ctx.accounts.reward_pool.as_mut(),
+            ctx.accounts.reward_tracker.as_mut(),
+            clock.unix_timestamp,
+        )?;
+        let task = &ctx.accounts.task;
+        log_activity(
+            ctx.accounts.activity_log.as_mut(),
+            task.board,
+            ctx.accounts.signer.key(),
+            Activity::Completed,
+            task.key(),
+        )?;
+        emit_cpi!(TaskCompleted {
+            task: task.key(),
+            task_id: task.task_id,
+            author: task.author,
+            completed_by: ctx.accounts.signer.key(),
+            completed_at: clock.unix_timestamp,
+        });
         msg!(
             "Task is marked complete. Title: {}, Author: {}",
             task.title,
@@ -68,12 +593,328 @@ pub mod task_manager {
         Ok(())
     }
 
-    pub fn delete_task(ctx: Context<DeleteTask>) -> Result<()> {
+    // Moving the task through its workflow
+    pub fn set_status(ctx: Context<SetStatus>, status: TaskStatus) -> Result<()> {
+        let events = EventAuthority {
+            info: ctx.accounts.event_authority.to_account_info(),
+            bump: ctx.bumps.event_authority,
+        };
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+        // Done goes through `complete_task` so its checks and `completed_at` can't be skipped
+        require!(status != TaskStatus::Done, ErrorTask::UseCompleteTask);
+        require!(
+            status != TaskStatus::PendingReview && task.status != TaskStatus::PendingReview,
+            ErrorTask::AwaitingReview
+        );
+        require!(
+            task.status.can_transition_to(status),
+            ErrorTask::InvalidStatusTransition
+        );
+        task.status = status;
+        task.updated_at = Clock::get()?.unix_timestamp;
+        if task.status == TaskStatus::Cancelled {
+            ctx.accounts.profile.record_cancelled();
+        }
+        log_activity(
+            ctx.accounts.activity_log.as_mut(),
+            task.board,
+            task.author,
+            Activity::StatusChanged,
+            task.key(),
+        )?;
+        emit_watched_status(&events, task)?;
+        msg!(
+            "Task status changed to {:?}. Title: {}, Author: {}",
+            task.status,
+            task.title,
+            task.author
+        );
+        Ok(())
+    }
+
+    pub fn reopen_task(ctx: Context<ReopenTask>) -> Result<()> {
+        let events = EventAuthority {
+            info: ctx.accounts.event_authority.to_account_info(),
+            bump: ctx.bumps.event_authority,
+        };
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+        let clock = Clock::get()?;
+
+        require!(task.is_completed(), ErrorTask::TaskNotCompleted);
+
+        task.status = TaskStatus::Todo;
+        task.completed_at = None;
+        task.updated_at = clock.unix_timestamp;
+        task.reopened_count = task.reopened_count.saturating_add(1);
+        task.approvals.clear();
+        ctx.accounts.profile.record_reopened();
+        if task.milestone.is_some() {
+            let milestone = ctx
+                .accounts
+                .milestone
+                .as_mut()
+                .ok_or(ErrorTask::MilestoneMismatch)?;
+            milestone.completed = milestone.completed.saturating_sub(1);
+            emit_milestone_progress(&events, milestone, task)?;
+        }
+        log_activity(
+            ctx.accounts.activity_log.as_mut(),
+            task.board,
+            task.author,
+            Activity::Reopened,
+            task.key(),
+        )?;
+
+        emit_watched_status(&events, task)?;
+        emit_cpi!(TaskReopened {
+            task: task.key(),
+            task_id: task.task_id,
+            author: task.author,
+            reopened_count: task.reopened_count,
+            timestamp: clock.unix_timestamp,
+        });
+        msg!(
+            "Task reopened. Title: {}, Author: {}, Reopened: {}",
+            task.title,
+            task.author,
+            task.reopened_count
+        );
+        Ok(())
+    }
+
+    pub fn set_priority(ctx: Context<SetPriority>, priority: Priority) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+        task.priority = priority;
+        msg!(
+            "Task priority changed to {:?}. Title: {}, Author: {}",
+            task.priority,
+            task.title,
+            task.author
+        );
+        Ok(())
+    }
+
+    pub fn add_tag(ctx: Context<AddTag>, tag: String) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+
+        require!(tag.len() <= 20, ErrorTask::TagTooLong);
+        require!(!tag.trim().is_empty(), ErrorTask::TagIsEmpty);
+        require!(task.tags.len() < 5, ErrorTask::TooManyTags);
+        require!(!task.tags.contains(&tag), ErrorTask::DuplicateTag);
+
+        task.tags.push(tag);
+        msg!(
+            "Tag added. Title: {}, Author: {}, Tags: {:?}",
+            task.title,
+            task.author,
+            task.tags
+        );
+        Ok(())
+    }
+
+    pub fn remove_tag(ctx: Context<RemoveTag>, tag: String) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+
+        let index = task
+            .tags
+            .iter()
+            .position(|t| *t == tag)
+            .ok_or(ErrorTask::TagNotFound)?;
+        task.tags.remove(index);
+        msg!(
+            "Tag removed. Title: {}, Author: {}, Tags: {:?}",
+            task.title,
+            task.author,
+            task.tags
+        );
+        Ok(())
+    }
+
+    pub fn add_dependency(ctx: Context<AddDependency>) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let dependency = ctx.accounts.dependency.key();
+        let task = &mut ctx.accounts.task;
+
+        require!(dependency != task.key(), ErrorTask::SelfDependency);
+        require!(task.blocked_by.len() < 10, ErrorTask::TooManyDependencies);
+        require!(
+            !task.blocked_by.contains(&dependency),
+            ErrorTask::DuplicateDependency
+        );
+
+        task.blocked_by.push(dependency);
+        let dependency_task = &mut ctx.accounts.dependency;
+        dependency_task.dependent_count = dependency_task.dependent_count.saturating_add(1);
+        msg!(
+            "Dependency added. Title: {}, Author: {}, Blocked by: {}",
+            task.title,
+            task.author,
+            dependency
+        );
+        Ok(())
+    }
+
+    pub fn remove_dependency(ctx: Context<RemoveDependency>, dependency: Pubkey) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+
+        let index = task
+            .blocked_by
+            .iter()
+            .position(|d| *d == dependency)
+            .ok_or(ErrorTask::DependencyNotFound)?;
+        task.blocked_by.remove(index);
+        // Left out when the dependency was deleted in the meantime
+        if let Some(dependency_task) = ctx.accounts.dependency_task.as_mut() {
+            require_keys_eq!(
+                dependency_task.key(),
+                dependency,
+                ErrorTask::DependencyNotFound
+            );
+            dependency_task.dependent_count = dependency_task.dependent_count.saturating_sub(1);
+        }
+        msg!(
+            "Dependency removed. Title: {}, Author: {}, Dependency: {}",
+            task.title,
+            task.author,
+            dependency
+        );
+        Ok(())
+    }
+
+    // Assigning the task to another wallet, `None` clears the assignee
+    pub fn assign_task(ctx: Context<AssignTask>, assignee: Option<Pubkey>) -> Result<()> {
+        let events = EventAuthority {
+            info: ctx.accounts.event_authority.to_account_info(),
+            bump: ctx.bumps.event_authority,
+        };
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+        task.assignee = assignee;
+        task.assignment_accepted = false;
+        if let Some(assignee) = assignee {
+            notify(&events, assignee, NotificationKind::Assigned, task)?;
+        }
+        msg!(
+            "Task assigned. Title: {}, Author: {}, Assignee: {:?}",
+            task.title,
+            task.author,
+            task.assignee
+        );
+        Ok(())
+    }
+
+    pub fn accept_assignment(ctx: Context<AcceptAssignment>) -> Result<()> {
+        let task = &mut ctx.accounts.task;
+        task.assignment_accepted = true;
+        msg!(
+            "Assignment accepted. Title: {}, Author: {}, Assignee: {}",
+            task.title,
+            task.author,
+            ctx.accounts.assignee.key()
+        );
+        Ok(())
+    }
+
+    // The parent and `blocked_by` tasks come in as remaining accounts so their
+    // `dependent_count` goes back down
+    pub fn delete_task<'info>(ctx: Context<'_, '_, 'info, 'info, DeleteTask<'info>>) -> Result<()> {
         let task = &ctx.accounts.task;
         require!(
-            task.author == ctx.accounts.author.key(),
+            task.authority == ctx.accounts.author.key(),
             ErrorTask::Unauthorized
         );
+        if let Some(board_key) = task.board {
+            let board = ctx
+                .accounts
+                .board
+                .as_mut()
+                .ok_or(ErrorTask::BoardMismatch)?;
+            require_keys_eq!(board.key(), board_key, ErrorTask::BoardMismatch);
+            board.task_count = board.task_count.saturating_sub(1);
+        }
+        require!(!task.has_token_bounty, ErrorTask::TokenBountyOpen);
+        require!(!task.has_stake, ErrorTask::StakeLocked);
+        require!(task.milestone.is_none(), ErrorTask::MilestoneLinked);
+        require!(task.sprint.is_none(), ErrorTask::SprintLinked);
+        require!(task.labels.is_empty(), ErrorTask::LabelsApplied);
+        require!(task.timer_started_at.is_none(), ErrorTask::TimerRunning);
+        count_against_dependencies(task, ctx.remaining_accounts, false)?;
+        // An open bounty goes back to its funder, a completed one belongs to the assignee
+        if task.has_bounty {
+            require!(!task.is_completed(), ErrorTask::BountyNotClaimed);
+            let escrow = ctx
+                .accounts
+                .escrow
+                .as_ref()
+                .ok_or(ErrorTask::EscrowMismatch)?;
+            let funder = ctx
+                .accounts
+                .funder
+                .as_ref()
+                .ok_or(ErrorTask::EscrowMismatch)?;
+            require_keys_eq!(funder.key(), escrow.funder, ErrorTask::EscrowMismatch);
+            require!(!escrow.disputed, ErrorTask::BountyDisputed);
+            escrow.close(funder.to_account_info())?;
+        }
+        let profile = &mut ctx.accounts.profile;
+        profile.record_deleted(task.is_open());
+        if task.is_pinned {
+            profile.pinned_count = profile.pinned_count.saturating_sub(1);
+        }
+        if let Some(task_index) = ctx.accounts.task_index.as_mut() {
+            task_index.remove(task.task_id);
+        }
+        log_activity(
+            ctx.accounts.activity_log.as_mut(),
+            task.board,
+            task.author,
+            Activity::Deleted,
+            task.key(),
+        )?;
+        emit_cpi!(TaskDeleted {
+            task: task.key(),
+            task_id: task.task_id,
+            author: task.author,
+            deleted_at: Clock::get()?.unix_timestamp,
+        });
         msg!(
             "Task Deleted. Title: {}, Author: {}",
             task.title,
@@ -82,79 +923,10121 @@ pub mod task_manager {
         Ok(())
     }
 
-}
+    // Funding a bounty on the task, the lamports sit in the task's escrow PDA
+    pub fn fund_task(ctx: Context<FundTask>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorTask::InvalidAmount);
+        // The due date is the refund deadline, without one a bounty could never be refunded
+        require!(
+            ctx.accounts.task.due_date.is_some(),
+            ErrorTask::DueDateRequired
+        );
+        require!(
+            !ctx.accounts.task.is_completed(),
+            ErrorTask::TaskAlreadyCompleted
+        );
 
-#[derive(Accounts)]
-#[instruction(title: String)]
-pub struct CreateTask<'info> {
-    #[account(mut)]
-    pub author: Signer<'info>,
-    #[account(
-        init,
-        payer= author,
-        space = 8 + Task::INIT_SPACE,
-        seeds = [b"task", author.key().as_ref(), title.as_bytes()],
-        bump,
-    )]
-    pub task: Account<'info, Task>,
-    pub system_program: Program<'info, System>,
-}
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.funder.to_account_info(),
+                    to: ctx.accounts.escrow.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
 
-#[derive(Accounts)]
-pub struct UpdateTask<'info> {
-    #[account(mut)]
-    pub author: Signer<'info>,
-    #[account(
-        mut,
-        seeds = [b"task", author.key().as_ref(), task.title.as_bytes()],
-        bump,
-    )]
-    pub task: Account<'info, Task>,
-}
+        let escrow = &mut ctx.accounts.escrow;
+        let task = &mut ctx.accounts.task;
+        escrow.task = task.key();
+        escrow.funder = ctx.accounts.funder.key();
+        escrow.amount = amount;
+        escrow.usd_amount = None;
+        escrow.deadline = task.due_date;
+        escrow.disputed = false;
+        escrow.worker = None;
+        task.has_bounty = true;
 
-#[derive(Accounts)]
-pub struct CompleteTask<'info> {
-    #[account(mut)]
-    pub author: Signer<'info>,
-    #[account(
-        mut,
-        seeds = [b"task", author.key().as_ref(), task.title.as_bytes()],
-        bump,
-    )]
-    pub task: Account<'info, Task>,
-}
+        msg!(
+            "Task funded. Title: {}, Funder: {}, Amount: {}",
+            task.title,
+            escrow.funder,
+            escrow.amount
+        );
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct DeleteTask<'info> {
-    #[account(mut)]
-    pub author: Signer<'info>,
-    #[account(
-        mut,
-        seeds = [b"task", author.key().as_ref(), task.title.as_bytes()],
-        bump,
-        close = author,
-    )]
-    pub task: Account<'info, Task>,
-}
+    // Paying the bounty to the assignee once the task is completed
+    // Funding a bounty worth `usd_amount` micro-USD, `max_lamports` is escrowed and whatever
+    // the claim doesn't need goes back to the funder
+    pub fn fund_task_usd(ctx: Context<FundTask>, usd_amount: u64, max_lamports: u64) -> Result<()> {
+        require!(usd_amount > 0 && max_lamports > 0, ErrorTask::InvalidAmount);
+        // The due date is the refund deadline, without one a bounty could never be refunded
+        require!(
+            ctx.accounts.task.due_date.is_some(),
+            ErrorTask::DueDateRequired
+        );
+        require!(
+            !ctx.accounts.task.is_completed(),
+            ErrorTask::TaskAlreadyCompleted
+        );
 
-#[account]
-#[derive(InitSpace)]
-pub struct Task {
-    pub author: Pubkey,
-    #[max_len(100)]
-    pub title: String,
-    #[max_len(1000)]
-    pub description: String,
-    pub is_completed: bool,
-    pub created_at: i64,
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.funder.to_account_info(),
+                    to: ctx.accounts.escrow.to_account_info(),
+                },
+            ),
+            max_lamports,
+        )?;
+
+        let escrow = &mut ctx.accounts.escrow;
+        let task = &mut ctx.accounts.task;
+        escrow.task = task.key();
+        escrow.funder = ctx.accounts.funder.key();
+        escrow.amount = max_lamports;
+        escrow.usd_amount = Some(usd_amount);
+        escrow.deadline = task.due_date;
+        escrow.disputed = false;
+        escrow.worker = None;
+        task.has_bounty = true;
+
+        msg!(
+            "Task funded in USD. Title: {}, Funder: {}, USD: {}, Cap: {}",
+            task.title,
+            escrow.funder,
+            usd_amount,
+            max_lamports
+        );
+        Ok(())
+    }
+
+    pub fn claim_bounty(ctx: Context<ClaimBounty>) -> Result<()> {
+        require_visible_to(&ctx.accounts.task, None, &ctx.accounts.assignee.key())?;
+        let amount = match ctx.accounts.escrow.usd_amount {
+            Some(usd_amount) => {
+                let price_update = ctx
+                    .accounts
+                    .price_update
+                    .as_ref()
+                    .ok_or(ErrorTask::PriceUnavailable)?;
+                usd_to_lamports(price_update, usd_amount)?.min(ctx.accounts.escrow.amount)
+            }
+            None => ctx.accounts.escrow.amount,
+        };
+
+        **ctx
+            .accounts
+            .escrow
+            .to_account_info()
+            .try_borrow_mut_lamports()? -= amount;
+        **ctx
+            .accounts
+            .assignee
+            .to_account_info()
+            .try_borrow_mut_lamports()? += amount;
+
+        let task = &mut ctx.accounts.task;
+        task.has_bounty = false;
+        msg!(
+            "Bounty claimed. Title: {}, Assignee: {}, Amount: {}",
+            task.title,
+            ctx.accounts.assignee.key(),
+            amount
+        );
+        Ok(())
+    }
+
+    // Funding a bounty in any SPL or Token-2022 mint, the tokens sit in the task PDA's
+    // associated token account. With a transfer fee the escrow records what actually arrived
+    pub fn fund_task_spl(ctx: Context<FundTaskSpl>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorTask::InvalidAmount);
+        // The due date is the refund deadline, without one a bounty could never be refunded
+        require!(
+            ctx.accounts.task.due_date.is_some(),
+            ErrorTask::DueDateRequired
+        );
+        require!(
+            !ctx.accounts.task.is_completed(),
+            ErrorTask::TaskAlreadyCompleted
+        );
+        let allowed_mints = &ctx.accounts.config.allowed_mints;
+        require!(
+            allowed_mints.is_empty() || allowed_mints.contains(&ctx.accounts.mint.key()),
+            ErrorTask::MintNotAllowed
+        );
+
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.funder_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: ctx.accounts.funder.to_account_info(),
+                },
+            ),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+        ctx.accounts.vault.reload()?;
+
+        let token_escrow = &mut ctx.accounts.token_escrow;
+        let task = &mut ctx.accounts.task;
+        token_escrow.task = task.key();
+        token_escrow.funder = ctx.accounts.funder.key();
+        token_escrow.mint = ctx.accounts.mint.key();
+        token_escrow.amount = ctx.accounts.vault.amount;
+        token_escrow.deadline = task.due_date;
+        task.has_token_bounty = true;
+
+        msg!(
+            "Task funded. Title: {}, Funder: {}, Mint: {}, Amount: {}",
+            task.title,
+            token_escrow.funder,
+            token_escrow.mint,
+            token_escrow.amount
+        );
+        Ok(())
+    }
+
+    // Paying the whole vault to the assignee and closing it back to the funder
+    pub fn claim_bounty_spl(ctx: Context<ClaimBountySpl>) -> Result<()> {
+        require_visible_to(&ctx.accounts.task, None, &ctx.accounts.assignee.key())?;
+        let amount = ctx.accounts.vault.amount;
+        let author = ctx.accounts.task.author;
+        let task_id = ctx.accounts.task.task_id.to_le_bytes();
+        let bump = [ctx.accounts.task.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"task", author.as_ref(), task_id.as_ref(), &bump]];
+        // Token-2022 withholds the fee on the assignee's side, they receive `amount - fee`
+        let fee = transfer_fee(&ctx.accounts.mint, amount)?;
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.assignee_token_account.to_account_info(),
+                    authority: ctx.accounts.task.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+        token_interface::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::CloseAccount {
+                account: ctx.accounts.vault.to_account_info(),
+                destination: ctx.accounts.funder.to_account_info(),
+                authority: ctx.accounts.task.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+
+        let task = &mut ctx.accounts.task;
+        task.has_token_bounty = false;
+        msg!(
+            "Bounty claimed. Title: {}, Assignee: {}, Mint: {}, Amount: {}, Fee: {}",
+            task.title,
+            ctx.accounts.assignee.key(),
+            ctx.accounts.mint.key(),
+            amount,
+            fee
+        );
+        Ok(())
+    }
+
+    // Mentions on a board task must be board members, on a personal task only the author
+    // and the assignee can be mentioned
+    pub fn post_comment(
+        ctx: Context<PostComment>,
+        body: String,
+        mentions: Vec<Pubkey>,
+    ) -> Result<()> {
+        let events = EventAuthority {
+            info: ctx.accounts.event_authority.to_account_info(),
+            bump: ctx.bumps.event_authority,
+        };
+        let comment = &mut ctx.accounts.comment;
+        let task = &mut ctx.accounts.task;
+        let clock = Clock::get()?;
+
+        validate_comment(&body)?;
+        require_visible_to(
+            task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        require!(mentions.len() <= 5, ErrorTask::TooManyMentions);
+        for (i, mentioned) in mentions.iter().enumerate() {
+            require!(
+                !mentions[..i].contains(mentioned),
+                ErrorTask::DuplicateMention
+            );
+            let allowed = match task.board {
+                Some(_) => {
+                    let board = ctx
+                        .accounts
+                        .board
+                        .as_ref()
+                        .ok_or(ErrorTask::BoardMismatch)?;
+                    board.role_of(mentioned).is_some()
+                }
+                None => *mentioned == task.author || task.assignee.as_ref() == Some(mentioned),
+            };
+            require!(allowed, ErrorTask::InvalidMention);
+        }
+
+        comment.task = task.key();
+        comment.author = ctx.accounts.author.key();
+        comment.comment_id = task.comment_count;
+        comment.body = body;
+        comment.created_at = clock.unix_timestamp;
+        task.comment_count = task
+            .comment_count
+            .checked_add(1)
+            .ok_or(ErrorTask::CommentCountOverflow)?;
+
+        for mentioned in mentions {
+            notify(&events, mentioned, NotificationKind::Mentioned, task)?;
+            emit_cpi!(CommentMention {
+                task: comment.task,
+                task_id: task.task_id,
+                comment: comment.key(),
+                author: comment.author,
+                mentioned,
+            });
+        }
+
+        msg!(
+            "Comment posted, Task: {}, Author: {}, Id: {}",
+            comment.task,
+            comment.author,
+            comment.comment_id
+        );
+        Ok(())
+    }
+
+    pub fn edit_comment(ctx: Context<EditComment>, body: String) -> Result<()> {
+        let comment = &mut ctx.accounts.comment;
+
+        validate_comment(&body)?;
+
+        comment.body = body;
+        msg!(
+            "Comment edited, Task: {}, Author: {}, Id: {}",
+            comment.task,
+            comment.author,
+            comment.comment_id
+        );
+        Ok(())
+    }
+
+    pub fn delete_comment(ctx: Context<DeleteComment>) -> Result<()> {
+        let comment = &ctx.accounts.comment;
+        msg!(
+            "Comment deleted, Task: {}, Author: {}, Id: {}",
+            comment.task,
+            comment.author,
+            comment.comment_id
+        );
+        Ok(())
+    }
+
+    pub fn create_board(ctx: Context<CreateBoard>, name: String) -> Result<()> {
+        let board = &mut ctx.accounts.board;
+        let profile = &mut ctx.accounts.profile;
+
+        require!(name.len() <= 50, ErrorTask::BoardNameTooLong);
+        require!(!name.trim().is_empty(), ErrorTask::BoardNameIsEmpty);
+
+        board.owner = ctx.accounts.owner.key();
+        board.board_id = profile.board_count;
+        board.name = name;
+        board.task_count = 0;
+        board.members = Vec::new();
+        board.authority = None;
+        ctx.accounts.activity_log.board = board.key();
+        profile.board_count = profile
+            .board_count
+            .checked_add(1)
+            .ok_or(ErrorTask::BoardCountOverflow)?;
+
+        msg!(
+            "Board created, Id: {}, Name: {}, Owner: {}",
+            board.board_id,
+            board.name,
+            board.owner
+        );
+        Ok(())
+    }
+
+    // Boards created before activity logs existed get theirs here
+    pub fn init_activity_log(ctx: Context<InitActivityLog>) -> Result<()> {
+        ctx.accounts.activity_log.board = ctx.accounts.board.key();
+        msg!("Activity log created, Board: {}", ctx.accounts.board.name);
+        Ok(())
+    }
+
+    // Members join through an invite they accept themselves, the inviter pays the invite's
+    // rent and gets it back once it's answered
+    pub fn invite_member(ctx: Context<InviteMember>, member: Pubkey, role: Role) -> Result<()> {
+        let board = &ctx.accounts.board;
+
+        require!(
+            member != board.owner && board.role_of(&member).is_none(),
+            ErrorTask::DuplicateMember
+        );
+        require!(board.members.len() < 10, ErrorTask::TooManyMembers);
+
+        let invite = &mut ctx.accounts.invite;
+        invite.board = board.key();
+        invite.member = member;
+        invite.role = role;
+        invite.inviter = ctx.accounts.admin.key();
+        invite.created_at = Clock::get()?.unix_timestamp;
+        emit_cpi!(MemberInvited {
+            board: board.key(),
+            member,
+            role,
+            inviter: invite.inviter,
+        });
+        Ok(())
+    }
+
+    pub fn accept_invite(ctx: Context<AcceptInvite>) -> Result<()> {
+        let board = &mut ctx.accounts.board;
+        let invite = &ctx.accounts.invite;
+
+        // Membership may have changed since the invite went out
+        require!(
+            board.role_of(&invite.member).is_none(),
+            ErrorTask::DuplicateMember
+        );
+        require!(board.members.len() < 10, ErrorTask::TooManyMembers);
+
+        board.members.push(Member {
+            pubkey: invite.member,
+            role: invite.role,
+        });
+        ctx.accounts.activity_log.record(
+            invite.inviter,
+            Activity::MemberAdded,
+            invite.member,
+            Clock::get()?.slot,
+        );
+        msg!(
+            "Member added. Board: {}, Member: {}, Role: {:?}",
+            board.name,
+            invite.member,
+            invite.role
+        );
+        Ok(())
+    }
+
+    pub fn decline_invite(ctx: Context<DeclineInvite>) -> Result<()> {
+        msg!(
+            "Invite declined. Board: {}, Member: {}",
+            ctx.accounts.invite.board,
+            ctx.accounts.invite.member
+        );
+        Ok(())
+    }
+
+    pub fn remove_member(ctx: Context<RemoveMember>, member: Pubkey) -> Result<()> {
+        let board = &mut ctx.accounts.board;
+
+        let index = board
+            .members
+            .iter()
+            .position(|m| m.pubkey == member)
+            .ok_or(ErrorTask::MemberNotFound)?;
+        board.members.remove(index);
+        ctx.accounts.activity_log.record(
+            ctx.accounts.admin.key(),
+            Activity::MemberRemoved,
+            member,
+            Clock::get()?.slot,
+        );
+        msg!("Member removed. Board: {}, Member: {}", board.name, member);
+        Ok(())
+    }
+
+    pub fn change_role(ctx: Context<ChangeRole>, member: Pubkey, role: Role) -> Result<()> {
+        let board = &mut ctx.accounts.board;
+
+        let entry = board
+            .members
+            .iter_mut()
+            .find(|m| m.pubkey == member)
+            .ok_or(ErrorTask::MemberNotFound)?;
+        entry.role = role;
+        ctx.accounts.activity_log.record(
+            ctx.accounts.admin.key(),
+            Activity::RoleChanged,
+            member,
+            Clock::get()?.slot,
+        );
+        msg!(
+            "Member role changed. Board: {}, Member: {}, Role: {:?}",
+            board.name,
+            member,
+            role
+        );
+        Ok(())
+    }
+
+    // Closing the board, it has to be emptied of tasks first
+    // Tasks still on the board come in as remaining accounts, each followed by its author,
+    // the author's profile and task index. Every one of them has to be passed, subtasks and
+    // tasks with dependencies are deleted with `delete_task` beforehand
+    pub fn close_board<'info>(ctx: Context<'_, '_, 'info, 'info, CloseBoard<'info>>) -> Result<()> {
+        let board_key = ctx.accounts.board.key();
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            ctx.remaining_accounts.len() % 4 == 0,
+            ErrorTask::BoardTaskAccountsMissing
+        );
+
+        let mut closed: u64 = 0;
+        for accounts in ctx.remaining_accounts.chunks(4) {
+            let [task_info, author, profile_info, index_info] = accounts else {
+                unreachable!()
+            };
+            require!(
+                task_info.is_writable
+                    && author.is_writable
+                    && profile_info.is_writable
+                    && index_info.is_writable,
+                ErrorTask::AccountNotWritable
+            );
+            let task: Account<Task> = Account::try_from(task_info)?;
+            let mut profile: Account<UserProfile> = Account::try_from(profile_info)?;
+            let mut task_index: Account<TaskIndex> = Account::try_from(index_info)?;
+
+            require!(task.board == Some(board_key), ErrorTask::BoardMismatch);
+            require_keys_eq!(author.key(), task.author, ErrorTask::Unauthorized);
+            require_keys_eq!(profile.author, task.author, ErrorTask::Unauthorized);
+            require_keys_eq!(task_index.author, task.author, ErrorTask::Unauthorized);
+            require!(!task.has_bounty, ErrorTask::BountyNotClaimed);
+            require!(!task.has_token_bounty, ErrorTask::TokenBountyOpen);
+            require!(!task.has_stake, ErrorTask::StakeLocked);
+            require!(task.milestone.is_none(), ErrorTask::MilestoneLinked);
+            require!(task.sprint.is_none(), ErrorTask::SprintLinked);
+            require!(task.labels.is_empty(), ErrorTask::LabelsApplied);
+            require!(task.timer_started_at.is_none(), ErrorTask::TimerRunning);
+            require!(
+                task.parent.is_none() && task.blocked_by.is_empty(),
+                ErrorTask::DependenciesLinked
+            );
+
+            profile.record_deleted(task.is_open());
+            if task.is_pinned {
+                profile.pinned_count = profile.pinned_count.saturating_sub(1);
+            }
+            task_index.remove(task.task_id);
+            emit_cpi!(TaskDeleted {
+                task: task.key(),
+                task_id: task.task_id,
+                author: task.author,
+                deleted_at: now,
+            });
+            profile.exit(&crate::ID)?;
+            task_index.exit(&crate::ID)?;
+            task.close(author.clone())?;
+            closed += 1;
+        }
+
+        let board = &ctx.accounts.board;
+        require!(board.task_count == closed, ErrorTask::BoardNotEmpty);
+        msg!(
+            "Board closed, Name: {}, Owner: {}, Tasks closed: {}",
+            board.name,
+            board.owner,
+            closed
+        );
+        Ok(())
+    }
+
+    // Locking lamports against the task's due date, refunded by an on-time `complete_task`
+    pub fn stake_task(ctx: Context<StakeTask>, amount: u64) -> Result<()> {
+        let clock = Clock::get()?;
+        let deadline = ctx
+            .accounts
+            .task
+            .due_date
+            .ok_or(ErrorTask::DueDateRequired)?;
+
+        require!(amount > 0, ErrorTask::InvalidAmount);
+        require!(deadline > clock.unix_timestamp, ErrorTask::DueDateInPast);
+        require!(
+            !ctx.accounts.task.is_completed(),
+            ErrorTask::TaskAlreadyCompleted
+        );
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.author.to_account_info(),
+                    to: ctx.accounts.stake.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let stake = &mut ctx.accounts.stake;
+        let task = &mut ctx.accounts.task;
+        stake.task = task.key();
+        stake.author = task.author;
+        stake.amount = amount;
+        stake.deadline = deadline;
+        stake.oracle = ctx.accounts.config.time_oracle;
+        task.has_stake = true;
+
+        msg!(
+            "Task staked. Title: {}, Author: {}, Amount: {}, Deadline: {}",
+            task.title,
+            task.author,
+            stake.amount,
+            stake.deadline
+        );
+        Ok(())
+    }
+
+    // Anyone can send an expired stake to the treasury, the rent goes back to the author
+    pub fn slash_expired(ctx: Context<SlashExpired>) -> Result<()> {
+        let amount = ctx.accounts.stake.amount;
+        // Oracle-backed stakes only count the deadline as passed once the feed says so
+        let now = match ctx.accounts.stake.oracle {
+            Some(oracle) => {
+                let feed = ctx
+                    .accounts
+                    .oracle
+                    .as_ref()
+                    .ok_or(ErrorTask::OracleMismatch)?;
+                require_keys_eq!(feed.key(), oracle, ErrorTask::OracleMismatch);
+                let data = feed.try_borrow_data()?;
+                let feed =
+                    PullFeedAccountData::parse(data).map_err(|_| ErrorTask::OracleMismatch)?;
+                feed.last_update_timestamp
+            }
+            None => Clock::get()?.unix_timestamp,
+        };
+
+        require!(
+            now > ctx.accounts.stake.deadline,
+            ErrorTask::DeadlineNotPassed
+        );
+        // The runtime rejects a system account left below the rent-exempt minimum, so an
+        // empty treasury can't take a stake smaller than that
+        require!(
+            ctx.accounts.treasury.lamports().saturating_add(amount)
+                >= Rent::get()?.minimum_balance(0),
+            ErrorTask::TreasuryNotRentExempt
+        );
+
+        **ctx
+            .accounts
+            .stake
+            .to_account_info()
+            .try_borrow_mut_lamports()? -= amount;
+        **ctx
+            .accounts
+            .treasury
+            .to_account_info()
+            .try_borrow_mut_lamports()? += amount;
+
+        let task = &mut ctx.accounts.task;
+        task.has_stake = false;
+        msg!(
+            "Stake slashed. Title: {}, Author: {}, Amount: {}",
+            task.title,
+            task.author,
+            amount
+        );
+        Ok(())
+    }
+
+    // Creating a zero-copy task for descriptions too big for `Task`, filled in with
+    // `update_task_large` since a 10KB description doesn't fit in one transaction
+    pub fn create_task_large(ctx: Context<CreateTaskLarge>, title: String) -> Result<()> {
+        let mut task = ctx.accounts.task.load_init()?;
+        let profile = &mut ctx.accounts.profile;
+        let clock = Clock::get()?;
+
+        validate_title(&title, &ctx.accounts.config)?;
+        require!(
+            title.len() <= TaskLarge::MAX_TITLE_LEN,
+            ErrorTask::TitleTooManyBytes
+        );
+        collect_creation_fee(
+            &ctx.accounts.config,
+            &ctx.accounts.author,
+            &ctx.accounts.treasury,
+            &ctx.accounts.system_program,
+        )?;
+
+        task.author = ctx.accounts.author.key();
+        task.task_id = profile.task_count;
+        task.created_at = clock.unix_timestamp;
+        task.updated_at = clock.unix_timestamp;
+        task.status = TaskStatus::Todo as u8;
+        task.title[..title.len()].copy_from_slice(title.as_bytes());
+        task.title_len = title.len() as u8;
+        task.description_len = 0;
+        profile.task_count = profile
+            .task_count
+            .checked_add(1)
+            .ok_or(ErrorTask::TaskCountOverflow)?;
+        require_open_slot(
+            profile,
+            &ctx.accounts.config,
+            ctx.accounts.subscription.as_ref(),
+        )?;
+        profile.record_created();
+        ctx.accounts.global_stats.record_created();
+
+        msg!(
+            "Large task create, Id: {}, Title: {}, Author: {}",
+            task.task_id,
+            title,
+            task.author
+        );
+        Ok(())
+    }
+
+    // Writing a chunk of the description at `offset`, chunks can overwrite or extend it
+    // but can't leave a gap
+    pub fn update_task_large(
+        ctx: Context<UpdateTaskLarge>,
+        offset: u32,
+        chunk: Vec<u8>,
+    ) -> Result<()> {
+        let mut task = ctx.accounts.task.load_mut()?;
+        let start = offset as usize;
+        let end = start
+            .checked_add(chunk.len())
+            .ok_or(ErrorTask::DescriptionTooManyBytes)?;
+
+        require!(
+            end <= TaskLarge::MAX_DESCRIPTION_LEN,
+            ErrorTask::DescriptionTooManyBytes
+        );
+        require!(
+            start <= task.description_len as usize,
+            ErrorTask::InvalidOffset
+        );
+
+        task.description[start..end].copy_from_slice(&chunk);
+        if end > task.description_len as usize {
+            task.description_len = end as u16;
+        }
+        task.updated_at = Clock::get()?.unix_timestamp;
+
+        msg!(
+            "Large task description updated, Id: {}, Author: {}, Length: {}",
+            task.task_id,
+            task.author,
+            task.description_len
+        );
+        Ok(())
+    }
+
+    pub fn delete_task_large(ctx: Context<DeleteTaskLarge>) -> Result<()> {
+        let task = ctx.accounts.task.load()?;
+        ctx.accounts
+            .profile
+            .record_deleted(task.status != TaskStatus::Done as u8);
+        msg!(
+            "Large task deleted, Id: {}, Author: {}",
+            task.task_id,
+            task.author
+        );
+        Ok(())
+    }
+
+    // Hiding a finished task from active views without closing it
+    pub fn archive_task(ctx: Context<ArchiveTask>) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+
+        require!(!task.is_archived, ErrorTask::AlreadyArchived);
+        require!(!task.is_open(), ErrorTask::TaskStillOpen);
+
+        task.is_archived = true;
+        task.updated_at = Clock::get()?.unix_timestamp;
+        msg!(
+            "Task archived. Title: {}, Author: {}",
+            task.title,
+            task.author
+        );
+        Ok(())
+    }
+
+    pub fn unarchive_task(ctx: Context<UnarchiveTask>) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+
+        require!(task.is_archived, ErrorTask::NotArchived);
+
+        task.is_archived = false;
+        task.updated_at = Clock::get()?.unix_timestamp;
+        msg!(
+            "Task unarchived. Title: {}, Author: {}",
+            task.title,
+            task.author
+        );
+        Ok(())
+    }
+
+    // Completing every task passed in remaining_accounts in one transaction, tasks that
+    // need extra accounts to complete (dependencies, stakes, boards) go through `complete_task`
+    pub fn complete_many<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CompleteMany<'info>>,
+    ) -> Result<()> {
+        let events = EventAuthority {
+            info: ctx.accounts.event_authority.to_account_info(),
+            bump: ctx.bumps.event_authority,
+        };
+        let author = ctx.accounts.author.key();
+        let clock = Clock::get()?;
+
+        for account_info in ctx.remaining_accounts.iter() {
+            require!(account_info.is_writable, ErrorTask::AccountNotWritable);
+            let mut task: Account<Task> = Account::try_from(account_info)?;
+
+            require_keys_eq!(task.authority, author, ErrorTask::Unauthorized);
+            // The counters land on the signer's profile, so it has to be the task's author too
+            require_keys_eq!(task.author, author, ErrorTask::Unauthorized);
+            require_board_editor(&task, None, &author)?;
+            require!(task.deleted_at.is_none(), ErrorTask::AlreadyTrashed);
+            require!(task.blocked_by.is_empty(), ErrorTask::MissingDependency);
+            require!(!task.has_stake, ErrorTask::StakeLocked);
+            require!(task.milestone.is_none(), ErrorTask::MilestoneMismatch);
+            require!(task.reviewer.is_none(), ErrorTask::ReviewRequired);
+            require!(!task.is_completed(), ErrorTask::AlreadyCompleted);
+            require!(
+                task.status.can_transition_to(TaskStatus::Done),
+                ErrorTask::InvalidStatusTransition
+            );
+
+            task.status = TaskStatus::Done;
+            task.completed_at = Some(clock.unix_timestamp);
+            task.updated_at = clock.unix_timestamp;
+            task.progress = 100;
+            if task.stop_timer(clock.unix_timestamp).is_some() {
+                ctx.accounts.profile.active_timer = None;
+            }
+            emit_estimate_variance(&events, &task)?;
+            emit_watched_status(&events, &task)?;
+            award_completion(
+                &events,
+                &mut task,
+                &mut ctx.accounts.profile,
+                &mut ctx.accounts.global_stats,
+                clock.unix_timestamp,
+            )?;
+            emit_cpi!(TaskCompleted {
+                task: task.key(),
+                task_id: task.task_id,
+                author,
+                completed_by: author,
+                completed_at: clock.unix_timestamp,
+            });
+            task.exit(&crate::ID)?;
+        }
+
+        msg!(
+            "Tasks marked complete. Author: {}, Count: {}",
+            author,
+            ctx.remaining_accounts.len()
+        );
+        Ok(())
+    }
+
+    // Copying a task into a fresh id, `title` overrides the source title when given
+    pub fn clone_task(ctx: Context<CloneTask>, title: Option<String>) -> Result<()> {
+        let source = &ctx.accounts.source;
+        let task = &mut ctx.accounts.task;
+        let profile = &mut ctx.accounts.profile;
+        let clock = Clock::get()?;
+
+        let title = title.unwrap_or_else(|| source.title.clone());
+        validate_title(&title, &ctx.accounts.config)?;
+        collect_creation_fee(
+            &ctx.accounts.config,
+            &ctx.accounts.author,
+            &ctx.accounts.treasury,
+            &ctx.accounts.system_program,
+        )?;
+
+        task.init(
+            ctx.accounts.author.key(),
+            profile.task_count,
+            title,
+            source.description.clone(),
+            None,
+            source.priority,
+            clock.unix_timestamp,
+        );
+
+        task.bump = ctx.bumps.task;
+        index_task(
+            ctx.accounts.task_index.as_mut(),
+            &ctx.accounts.author,
+            &ctx.accounts.system_program,
+            task.task_id,
+        )?;
+        task.tags = source.tags.clone();
+        task.content_hash = source.content_hash;
+        task.is_private = source.is_private;
+        // The clone starts with the same checklist, nothing checked off
+        task.checklist = source
+            .checklist
+            .iter()
+            .map(|item| ChecklistItem {
+                text: item.text.clone(),
+                done: false,
+            })
+            .collect();
+        profile.task_count = profile
+            .task_count
+            .checked_add(1)
+            .ok_or(ErrorTask::TaskCountOverflow)?;
+        require_open_slot(
+            profile,
+            &ctx.accounts.config,
+            ctx.accounts.subscription.as_ref(),
+        )?;
+        profile.record_created();
+        ctx.accounts.global_stats.record_created();
+
+        emit_cpi!(TaskCreated {
+            task: task.key(),
+            task_id: task.task_id,
+            author: task.author,
+            created_at: task.created_at,
+        });
+        msg!(
+            "Task cloned, Id: {}, Source: {}, Title: {}, Author: {}",
+            task.task_id,
+            source.task_id,
+            task.title,
+            task.author
+        );
+        Ok(())
+    }
+
+    // Offering the task to another wallet, `None` cancels a pending offer
+    pub fn propose_transfer(
+        ctx: Context<ProposeTransfer>,
+        new_author: Option<Pubkey>,
+    ) -> Result<()> {
+        let task = &mut ctx.accounts.task;
+        require!(new_author != Some(task.author), ErrorTask::TransferToSelf);
+        require!(
+            new_author.is_none() || task.timer_started_at.is_none(),
+            ErrorTask::TimerRunning
+        );
+        task.pending_author = new_author;
+        msg!(
+            "Task transfer proposed. Title: {}, Author: {}, New author: {:?}",
+            task.title,
+            task.author,
+            task.pending_author
+        );
+        Ok(())
+    }
+
+    // Tasks are seeded by author, so accepting moves the task into a new PDA under the
+    // new author's next id and closes the old one back to the previous author
+    pub fn accept_transfer(ctx: Context<AcceptTransfer>) -> Result<()> {
+        let old_task = &ctx.accounts.old_task;
+        let profile = &mut ctx.accounts.profile;
+        let clock = Clock::get()?;
+
+        // The escrow and stake PDAs are seeded by the old task address
+        require!(
+            !old_task.has_bounty && !old_task.has_token_bounty && !old_task.has_stake,
+            ErrorTask::TaskHasFunds
+        );
+        // Milestones belong to the previous author
+        require!(old_task.milestone.is_none(), ErrorTask::MilestoneLinked);
+        require!(old_task.sprint.is_none(), ErrorTask::SprintLinked);
+        require!(old_task.labels.is_empty(), ErrorTask::LabelsApplied);
+        // The previous author's `active_timer` would keep pointing at the closed task
+        require!(old_task.timer_started_at.is_none(), ErrorTask::TimerRunning);
+        // Comments, subtasks and dependents all point at the old address
+        require!(
+            old_task.comment_count == 0 && old_task.dependent_count == 0,
+            ErrorTask::HasDependents
+        );
+
+        let mut data = (**old_task).clone();
+        data.author = ctx.accounts.new_author.key();
+        data.authority = ctx.accounts.new_author.key();
+        data.task_id = profile.task_count;
+        data.pending_author = None;
+        // Pins are per author, the new author starts with it unpinned
+        data.is_pinned = false;
+        data.bump = ctx.bumps.task;
+        data.updated_at = clock.unix_timestamp;
+        require_board_editor(
+            &data,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.new_author.key(),
+        )?;
+        index_task(
+            ctx.accounts.task_index.as_mut(),
+            &ctx.accounts.new_author,
+            &ctx.accounts.system_program,
+            data.task_id,
+        )?;
+        if let Some(task_index) = ctx.accounts.old_task_index.as_mut() {
+            task_index.remove(old_task.task_id);
+        }
+        ctx.accounts.task.set_inner(data);
+        profile.task_count = profile
+            .task_count
+            .checked_add(1)
+            .ok_or(ErrorTask::TaskCountOverflow)?;
+        if old_task.is_pinned {
+            let old_profile = &mut ctx.accounts.old_profile;
+            old_profile.pinned_count = old_profile.pinned_count.saturating_sub(1);
+        }
+        if old_task.is_open() {
+            require_open_slot(
+                profile,
+                &ctx.accounts.config,
+                ctx.accounts.subscription.as_ref(),
+            )?;
+            profile.open_count = profile.open_count.saturating_add(1);
+            let old_profile = &mut ctx.accounts.old_profile;
+            old_profile.open_count = old_profile.open_count.saturating_sub(1);
+        }
+
+        let task = &ctx.accounts.task;
+        emit_cpi!(TaskTransferred {
+            old_task: old_task.key(),
+            task: task.key(),
+            task_id: task.task_id,
+            previous_author: old_task.author,
+            author: task.author,
+            timestamp: clock.unix_timestamp,
+        });
+        msg!(
+            "Task transferred. Title: {}, Previous author: {}, Author: {}",
+            task.title,
+            old_task.author,
+            task.author
+        );
+        Ok(())
+    }
+
+    // Incident switch, while paused every state-changing instruction is rejected
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.paused = paused;
+        msg!("Program paused: {}", config.paused);
+        Ok(())
+    }
+
+    // Sending collected fees (and slashed stakes) from the treasury to the admin, the
+    // rent-exempt minimum stays behind so later slashes can always land
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorTask::InvalidAmount);
+        require!(
+            ctx.accounts.treasury.lamports().saturating_sub(amount)
+                >= Rent::get()?.minimum_balance(0),
+            ErrorTask::TreasuryNotRentExempt
+        );
+
+        let bump = [ctx.bumps.treasury];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"treasury", &bump]];
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.treasury.to_account_info(),
+                    to: ctx.accounts.admin.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        msg!(
+            "Fees withdrawn, Admin: {}, Amount: {}",
+            ctx.accounts.admin.key(),
+            amount
+        );
+        Ok(())
+    }
+
+    // Creating a recurring definition, `deposit` lamports are kept on the account to pay
+    // back whoever cranks `spawn_recurrence` for the rent of each new task
+    pub fn create_recurring(
+        ctx: Context<CreateRecurring>,
+        title: String,
+        description: String,
+        interval: i64,
+        first_due: i64,
+        deposit: u64,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+
+        validate_title(&title, &ctx.accounts.config)?;
+        validate_description(&description, false, &ctx.accounts.config)?;
+        validate_due_date(Some(first_due), clock.unix_timestamp)?;
+        require!(interval > 0, ErrorTask::InvalidInterval);
+
+        if deposit > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.author.to_account_info(),
+                        to: ctx.accounts.recurring.to_account_info(),
+                    },
+                ),
+                deposit,
+            )?;
+        }
+
+        let recurring = &mut ctx.accounts.recurring;
+        let profile = &mut ctx.accounts.profile;
+        recurring.author = ctx.accounts.author.key();
+        recurring.recurring_id = profile.recurring_count;
+        recurring.title = title;
+        recurring.description = description;
+        recurring.interval = interval;
+        recurring.next_due = first_due;
+        recurring.spawned_count = 0;
+        profile.recurring_count = profile
+            .recurring_count
+            .checked_add(1)
+            .ok_or(ErrorTask::RecurringCountOverflow)?;
+
+        msg!(
+            "Recurring task created, Id: {}, Title: {}, Author: {}, Next due: {}",
+            recurring.recurring_id,
+            recurring.title,
+            recurring.author,
+            recurring.next_due
+        );
+        Ok(())
+    }
+
+    // Anyone can spawn the next instance once `next_due` has passed, missed periods are
+    // skipped so a late crank only ever creates one task
+    pub fn spawn_recurrence(ctx: Context<SpawnRecurrence>) -> Result<()> {
+        let clock = Clock::get()?;
+        let recurring = &mut ctx.accounts.recurring;
+        let task = &mut ctx.accounts.task;
+        let profile = &mut ctx.accounts.profile;
+
+        require!(
+            clock.unix_timestamp >= recurring.next_due,
+            ErrorTask::RecurrenceNotDue
+        );
+
+        let missed = (clock.unix_timestamp - recurring.next_due) / recurring.interval + 1;
+        recurring.next_due = missed
+            .checked_mul(recurring.interval)
+            .and_then(|step| recurring.next_due.checked_add(step))
+            .ok_or(ErrorTask::InvalidInterval)?;
+        recurring.spawned_count = recurring.spawned_count.saturating_add(1);
+
+        // The cranker fronts the creation fee and the new task's rent, the deposit
+        // pays both back
+        collect_creation_fee(
+            &ctx.accounts.config,
+            &ctx.accounts.cranker,
+            &ctx.accounts.treasury,
+            &ctx.accounts.system_program,
+        )?;
+        let recurring_info = recurring.to_account_info();
+        let rent = Rent::get()?;
+        let refund = rent
+            .minimum_balance(task.to_account_info().data_len())
+            .saturating_add(ctx.accounts.config.creation_fee);
+        let spare = recurring_info
+            .lamports()
+            .saturating_sub(rent.minimum_balance(recurring_info.data_len()));
+        require!(spare >= refund, ErrorTask::InsufficientDeposit);
+        **recurring_info.try_borrow_mut_lamports()? -= refund;
+        **ctx
+            .accounts
+            .cranker
+            .to_account_info()
+            .try_borrow_mut_lamports()? += refund;
+
+        task.init(
+            recurring.author,
+            profile.task_count,
+            recurring.title.clone(),
+            recurring.description.clone(),
+            Some(recurring.next_due),
+            Priority::Medium,
+            clock.unix_timestamp,
+        );
+
+        task.bump = ctx.bumps.task;
+        index_task(
+            ctx.accounts.task_index.as_mut(),
+            &ctx.accounts.cranker,
+            &ctx.accounts.system_program,
+            task.task_id,
+        )?;
+        profile.task_count = profile
+            .task_count
+            .checked_add(1)
+            .ok_or(ErrorTask::TaskCountOverflow)?;
+        require_open_slot(
+            profile,
+            &ctx.accounts.config,
+            ctx.accounts.subscription.as_ref(),
+        )?;
+        profile.record_created();
+        ctx.accounts.global_stats.record_created();
+
+        emit_cpi!(TaskCreated {
+            task: task.key(),
+            task_id: task.task_id,
+            author: task.author,
+            created_at: task.created_at,
+        });
+        msg!(
+            "Recurring task spawned, Id: {}, Title: {}, Author: {}, Next due: {}",
+            task.task_id,
+            task.title,
+            task.author,
+            recurring.next_due
+        );
+        Ok(())
+    }
+
+    // Stopping a recurrence, the remaining deposit goes back to the author
+    pub fn cancel_recurring(ctx: Context<CancelRecurring>) -> Result<()> {
+        let recurring = &ctx.accounts.recurring;
+        msg!(
+            "Recurring task cancelled, Id: {}, Title: {}, Author: {}",
+            recurring.recurring_id,
+            recurring.title,
+            recurring.author
+        );
+        Ok(())
+    }
+
+    // Registering reminder settings, `thread` is the automation thread (e.g. a Clockwork
+    // thread created client-side with a `tick` instruction) allowed to tick for the author
+    pub fn init_reminder(
+        ctx: Context<InitReminder>,
+        due_soon_window: i64,
+        thread: Option<Pubkey>,
+    ) -> Result<()> {
+        require!(due_soon_window > 0, ErrorTask::InvalidInterval);
+
+        let reminder = &mut ctx.accounts.reminder;
+        reminder.author = ctx.accounts.author.key();
+        reminder.due_soon_window = due_soon_window;
+        reminder.thread = thread;
+        msg!(
+            "Reminder created, Author: {}, Window: {}, Thread: {:?}",
+            reminder.author,
+            reminder.due_soon_window,
+            reminder.thread
+        );
+        Ok(())
+    }
+
+    pub fn update_reminder(
+        ctx: Context<UpdateReminder>,
+        due_soon_window: i64,
+        thread: Option<Pubkey>,
+    ) -> Result<()> {
+        require!(due_soon_window > 0, ErrorTask::InvalidInterval);
+
+        let reminder = &mut ctx.accounts.reminder;
+        reminder.due_soon_window = due_soon_window;
+        reminder.thread = thread;
+        msg!(
+            "Reminder updated, Author: {}, Window: {}, Thread: {:?}",
+            reminder.author,
+            reminder.due_soon_window,
+            reminder.thread
+        );
+        Ok(())
+    }
+
+    // Emitting due-soon and overdue events for the author's tasks passed in remaining_accounts,
+    // nothing is written so threads can call it as often as they like
+    pub fn tick<'info>(ctx: Context<'_, '_, 'info, 'info, Tick<'info>>) -> Result<()> {
+        let events = EventAuthority {
+            info: ctx.accounts.event_authority.to_account_info(),
+            bump: ctx.bumps.event_authority,
+        };
+        let reminder = &ctx.accounts.reminder;
+        let now = Clock::get()?.unix_timestamp;
+
+        for account_info in ctx.remaining_accounts.iter() {
+            let task: Account<Task> = Account::try_from(account_info)?;
+            require_keys_eq!(task.author, reminder.author, ErrorTask::Unauthorized);
+
+            let due_date = match task.due_date {
+                Some(due_date) if task.is_open() && task.deleted_at.is_none() => due_date,
+                _ => continue,
+            };
+            if task.is_overdue(now) {
+                notify(&events, task.author, NotificationKind::Overdue, &task)?;
+                emit_cpi!(TaskOverdue {
+                    task: task.key(),
+                    task_id: task.task_id,
+                    author: task.author,
+                    due_date,
+                    timestamp: now,
+                });
+            } else if due_date - now <= reminder.due_soon_window {
+                notify(&events, task.author, NotificationKind::DueSoon, &task)?;
+                emit_cpi!(TaskDueSoon {
+                    task: task.key(),
+                    task_id: task.task_id,
+                    author: task.author,
+                    due_date,
+                    timestamp: now,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn set_expiry_params(
+        ctx: Context<SetExpiryParams>,
+        expiry_grace: i64,
+        expiry_tip: u64,
+    ) -> Result<()> {
+        require!(expiry_grace >= 0, ErrorTask::InvalidLimit);
+
+        let config = &mut ctx.accounts.config;
+        config.expiry_grace = expiry_grace;
+        config.expiry_tip = expiry_tip;
+        msg!(
+            "Expiry params updated, Grace: {}, Tip: {}",
+            config.expiry_grace,
+            config.expiry_tip
+        );
+        Ok(())
+    }
+
+    // Anyone can expire a task once its due date plus the configured grace has passed,
+    // the cranker is tipped out of the bounty escrow when one is passed in
+    pub fn expire_task(ctx: Context<ExpireTask>) -> Result<()> {
+        let events = EventAuthority {
+            info: ctx.accounts.event_authority.to_account_info(),
+            bump: ctx.bumps.event_authority,
+        };
+        let clock = Clock::get()?;
+        let config = &ctx.accounts.config;
+        let task = &mut ctx.accounts.task;
+
+        let due_date = task.due_date.ok_or(ErrorTask::DueDateRequired)?;
+        require!(task.is_open(), ErrorTask::TaskNotOpen);
+        require!(
+            clock.unix_timestamp > due_date.saturating_add(config.expiry_grace),
+            ErrorTask::DeadlineNotPassed
+        );
+
+        task.status = TaskStatus::Expired;
+        task.updated_at = clock.unix_timestamp;
+        emit_watched_status(&events, task)?;
+        ctx.accounts.profile.record_expired();
+
+        let mut tip = 0;
+        if let Some(escrow) = ctx.accounts.escrow.as_mut() {
+            tip = config.expiry_tip.min(escrow.amount);
+            escrow.amount -= tip;
+            **escrow.to_account_info().try_borrow_mut_lamports()? -= tip;
+            **ctx
+                .accounts
+                .cranker
+                .to_account_info()
+                .try_borrow_mut_lamports()? += tip;
+        }
+
+        msg!(
+            "Task expired. Title: {}, Author: {}, Tip: {}",
+            task.title,
+            task.author,
+            tip
+        );
+        Ok(())
+    }
+
+    // Minting a frozen, fixed-supply badge for a task that ran at least a week or for an
+    // author past their tenth completion, one badge per task
+    pub fn mint_completion_badge(ctx: Context<MintCompletionBadge>, uri: String) -> Result<()> {
+        let task = &ctx.accounts.task;
+        let completed_at = task.completed_at.ok_or(ErrorTask::TaskNotCompleted)?;
+
+        require!(uri.len() <= 200, ErrorTask::UriTooLong);
+        require!(!task.badge_minted, ErrorTask::BadgeAlreadyMinted);
+        require!(
+            completed_at - task.created_at >= Task::BADGE_MIN_DURATION
+                || ctx.accounts.profile.tasks_completed >= UserProfile::BADGE_MIN_COMPLETIONS,
+            ErrorTask::BadgeNotEarned
+        );
+
+        let bump = [ctx.bumps.badge_authority];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"badge_authority", &bump]];
+
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::MintTo {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.token_account.to_account_info(),
+                    authority: ctx.accounts.badge_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            1,
+        )?;
+        create_metadata_accounts_v3(
+            CpiContext::new_with_signer(
+                ctx.accounts.metadata_program.to_account_info(),
+                CreateMetadataAccountsV3 {
+                    metadata: ctx.accounts.metadata.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    mint_authority: ctx.accounts.badge_authority.to_account_info(),
+                    payer: ctx.accounts.author.to_account_info(),
+                    update_authority: ctx.accounts.badge_authority.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            DataV2 {
+                name: format!("Task Badge #{}", task.task_id),
+                symbol: "TASK".to_string(),
+                uri,
+                seller_fee_basis_points: 0,
+                creators: None,
+                collection: None,
+                uses: None,
+            },
+            false,
+            true,
+            None,
+        )?;
+        // Frozen so it can't be transferred, and no mint authority so the supply stays at 1
+        token::freeze_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::FreezeAccount {
+                account: ctx.accounts.token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                authority: ctx.accounts.badge_authority.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+        token::set_authority(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token::SetAuthority {
+                    current_authority: ctx.accounts.badge_authority.to_account_info(),
+                    account_or_mint: ctx.accounts.mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            token::spl_token::instruction::AuthorityType::MintTokens,
+            None,
+        )?;
+
+        let task = &mut ctx.accounts.task;
+        task.badge_minted = true;
+        msg!(
+            "Completion badge minted. Title: {}, Author: {}, Mint: {}",
+            task.title,
+            task.author,
+            ctx.accounts.mint.key()
+        );
+        Ok(())
+    }
+
+    // The tree has to be created beforehand with the badge authority PDA as its delegate,
+    // None turns compressed badges off
+    pub fn set_badge_tree(ctx: Context<SetBadgeTree>, badge_tree: Option<Pubkey>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.badge_tree = badge_tree;
+        msg!("Badge tree updated, Tree: {:?}", config.badge_tree);
+        Ok(())
+    }
+
+    // Same qualification as `mint_completion_badge` but minted as a compressed NFT into the
+    // configured tree, a task still only gets one badge of either kind
+    pub fn mint_cnft_badge(ctx: Context<MintCnftBadge>, uri: String) -> Result<()> {
+        let task = &ctx.accounts.task;
+        let completed_at = task.completed_at.ok_or(ErrorTask::TaskNotCompleted)?;
+
+        require!(uri.len() <= 200, ErrorTask::UriTooLong);
+        require!(!task.badge_minted, ErrorTask::BadgeAlreadyMinted);
+        require!(
+            completed_at - task.created_at >= Task::BADGE_MIN_DURATION
+                || ctx.accounts.profile.tasks_completed >= UserProfile::BADGE_MIN_COMPLETIONS,
+            ErrorTask::BadgeNotEarned
+        );
+
+        let bump = [ctx.bumps.badge_authority];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"badge_authority", &bump]];
+
+        MintV1CpiBuilder::new(&ctx.accounts.bubblegum_program.to_account_info())
+            .tree_config(&ctx.accounts.tree_config.to_account_info())
+            .leaf_owner(&ctx.accounts.author.to_account_info())
+            .leaf_delegate(&ctx.accounts.author.to_account_info())
+            .merkle_tree(&ctx.accounts.merkle_tree.to_account_info())
+            .payer(&ctx.accounts.author.to_account_info())
+            .tree_creator_or_delegate(&ctx.accounts.badge_authority.to_account_info())
+            .log_wrapper(&ctx.accounts.log_wrapper.to_account_info())
+            .compression_program(&ctx.accounts.compression_program.to_account_info())
+            .system_program(&ctx.accounts.system_program.to_account_info())
+            .metadata(MetadataArgs {
+                name: format!("Task Badge #{}", task.task_id),
+                symbol: "TASK".to_string(),
+                uri,
+                seller_fee_basis_points: 0,
+                primary_sale_happened: false,
+                is_mutable: false,
+                edition_nonce: None,
+                token_standard: Some(TokenStandard::NonFungible),
+                collection: None,
+                uses: None,
+                token_program_version: TokenProgramVersion::Original,
+                creators: vec![],
+            })
+            .invoke_signed(signer_seeds)?;
+
+        let task = &mut ctx.accounts.task;
+        task.badge_minted = true;
+        msg!(
+            "Compressed badge minted. Title: {}, Author: {}, Tree: {}",
+            task.title,
+            task.author,
+            ctx.accounts.merkle_tree.key()
+        );
+        Ok(())
+    }
+
+    pub fn init_leaderboard(ctx: Context<InitLeaderboard>) -> Result<()> {
+        ctx.accounts.leaderboard.entries = Vec::new();
+        msg!("Leaderboard created");
+        Ok(())
+    }
+
+    // Anyone can push a profile's current XP onto the leaderboard, it's kept sorted
+    // highest first and capped at `Leaderboard::SIZE` entries
+    pub fn update_leaderboard(ctx: Context<UpdateLeaderboard>) -> Result<()> {
+        let profile = &ctx.accounts.profile;
+        let rank = ctx.accounts.leaderboard.submit(profile.author, profile.xp);
+        msg!(
+            "Leaderboard updated, Author: {}, Score: {}, Rank: {:?}",
+            profile.author,
+            profile.xp,
+            rank
+        );
+        Ok(())
+    }
+
+    pub fn create_achievement(
+        ctx: Context<CreateAchievement>,
+        achievement_id: u64,
+        name: String,
+        criteria: AchievementCriteria,
+        threshold: u64,
+        reward_xp: u64,
+    ) -> Result<()> {
+        require!(name.len() <= 32, ErrorTask::AchievementNameTooLong);
+
+        let achievement = &mut ctx.accounts.achievement;
+        achievement.achievement_id = achievement_id;
+        achievement.name = name;
+        achievement.criteria = criteria;
+        achievement.threshold = threshold;
+        achievement.reward_xp = reward_xp;
+        msg!(
+            "Achievement created. Name: {}, Id: {}",
+            achievement.name,
+            achievement.achievement_id
+        );
+        Ok(())
+    }
+
+    // The unlock PDA can only be created once, so every achievement pays out one time
+    pub fn unlock_achievement(ctx: Context<UnlockAchievement>) -> Result<()> {
+        let events = EventAuthority {
+            info: ctx.accounts.event_authority.to_account_info(),
+            bump: ctx.bumps.event_authority,
+        };
+        let achievement = &ctx.accounts.achievement;
+        require!(
+            achievement.is_met(&ctx.accounts.profile),
+            ErrorTask::AchievementNotMet
+        );
+
+        let unlock = &mut ctx.accounts.unlock;
+        unlock.author = ctx.accounts.author.key();
+        unlock.achievement = achievement.key();
+        unlock.unlocked_at = Clock::get()?.unix_timestamp;
+        award_xp(&events, &mut ctx.accounts.profile, achievement.reward_xp)?;
+        msg!(
+            "Achievement unlocked. Name: {}, Author: {}",
+            achievement.name,
+            unlock.author
+        );
+        Ok(())
+    }
+
+    pub fn init_reputation(ctx: Context<InitReputation>, assignee: Pubkey) -> Result<()> {
+        let reputation = &mut ctx.accounts.reputation;
+        reputation.assignee = assignee;
+        reputation.completions = 0;
+        reputation.on_time = 0;
+        msg!("Reputation created, Assignee: {}", reputation.assignee);
+        Ok(())
+    }
+
+    // The author signs off on work an accepted assignee completed, counted once per task
+    pub fn accept_work(ctx: Context<AcceptWork>) -> Result<()> {
+        let task = &mut ctx.accounts.task;
+        let completed_at = task.completed_at.ok_or(ErrorTask::TaskNotCompleted)?;
+        require!(task.assignment_accepted, ErrorTask::AssignmentNotAccepted);
+        require!(
+            !task.reputation_recorded,
+            ErrorTask::ReputationAlreadyRecorded
+        );
+
+        let reputation = &mut ctx.accounts.reputation;
+        reputation.completions = reputation.completions.saturating_add(1);
+        if task
+            .due_date
+            .map_or(true, |due_date| completed_at <= due_date)
+        {
+            reputation.on_time = reputation.on_time.saturating_add(1);
+        }
+        task.reputation_recorded = true;
+        msg!(
+            "Work accepted. Title: {}, Assignee: {}, On time rate: {}bps",
+            task.title,
+            reputation.assignee,
+            reputation.on_time_rate_bps()
+        );
+        Ok(())
+    }
+
+    pub fn add_item(ctx: Context<AddItem>, text: String) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+
+        require!(text.len() <= 50, ErrorTask::ItemTooLong);
+        require!(!text.trim().is_empty(), ErrorTask::ItemIsEmpty);
+        require!(task.checklist.len() < 10, ErrorTask::TooManyItems);
+
+        task.checklist.push(ChecklistItem { text, done: false });
+        task.update_progress();
+        msg!(
+            "Checklist item added. Title: {}, Author: {}, Progress: {}%",
+            task.title,
+            task.author,
+            task.progress
+        );
+        Ok(())
+    }
+
+    pub fn toggle_item(ctx: Context<ToggleItem>, index: u8) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+
+        let item = task
+            .checklist
+            .get_mut(index as usize)
+            .ok_or(ErrorTask::ItemNotFound)?;
+        item.done = !item.done;
+        task.update_progress();
+        msg!(
+            "Checklist item toggled. Title: {}, Author: {}, Progress: {}%",
+            task.title,
+            task.author,
+            task.progress
+        );
+        Ok(())
+    }
+
+    pub fn remove_item(ctx: Context<RemoveItem>, index: u8) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+
+        require!(
+            (index as usize) < task.checklist.len(),
+            ErrorTask::ItemNotFound
+        );
+        task.checklist.remove(index as usize);
+        task.update_progress();
+        msg!(
+            "Checklist item removed. Title: {}, Author: {}, Progress: {}%",
+            task.title,
+            task.author,
+            task.progress
+        );
+        Ok(())
+    }
+
+    pub fn set_progress(ctx: Context<SetProgress>, progress: u8) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+
+        require!(progress <= 100, ErrorTask::InvalidProgress);
+        require!(
+            !task.is_completed() || progress >= task.progress,
+            ErrorTask::ProgressLowered
+        );
+
+        task.progress = progress;
+        task.updated_at = Clock::get()?.unix_timestamp;
+        msg!(
+            "Progress updated. Title: {}, Author: {}, Progress: {}%",
+            task.title,
+            task.author,
+            task.progress
+        );
+        Ok(())
+    }
+
+    // Linking an off-chain file (IPFS, Arweave, ...) by its sha256 so clients can verify it
+    pub fn add_attachment(
+        ctx: Context<AddAttachment>,
+        sha256: [u8; 32],
+        uri: String,
+    ) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+
+        require!(uri.len() <= 200, ErrorTask::UriTooLong);
+        require!(!uri.trim().is_empty(), ErrorTask::UriIsEmpty);
+        require!(task.attachments.len() < 5, ErrorTask::TooManyAttachments);
+        require!(
+            !task.attachments.iter().any(|a| a.sha256 == sha256),
+            ErrorTask::DuplicateAttachment
+        );
+
+        task.attachments.push(Attachment { sha256, uri });
+        task.updated_at = Clock::get()?.unix_timestamp;
+        msg!(
+            "Attachment added. Title: {}, Author: {}, Attachments: {}",
+            task.title,
+            task.author,
+            task.attachments.len()
+        );
+        Ok(())
+    }
+
+    pub fn remove_attachment(ctx: Context<RemoveAttachment>, sha256: [u8; 32]) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+
+        let index = task
+            .attachments
+            .iter()
+            .position(|a| a.sha256 == sha256)
+            .ok_or(ErrorTask::AttachmentNotFound)?;
+        task.attachments.remove(index);
+        task.updated_at = Clock::get()?.unix_timestamp;
+        msg!(
+            "Attachment removed. Title: {}, Author: {}, Attachments: {}",
+            task.title,
+            task.author,
+            task.attachments.len()
+        );
+        Ok(())
+    }
+
+    // Switching between plain and encrypted mode, the description is replaced in the same
+    // instruction so plain text never sits in a private task or the other way round
+    pub fn set_private(
+        ctx: Context<SetPrivate>,
+        is_private: bool,
+        description: String,
+    ) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+
+        require!(task.content_hash.is_none(), ErrorTask::ContentIsHashed);
+        validate_description(&description, is_private, &ctx.accounts.config)?;
+
+        task.is_private = is_private;
+        task.description = description;
+        task.updated_at = Clock::get()?.unix_timestamp;
+        emit_cpi!(TaskUpdated {
+            task: task.key(),
+            task_id: task.task_id,
+            author: task.author,
+            updated_at: task.updated_at,
+        });
+        msg!(
+            "Task privacy updated, Title: {}, Author: {}, Private: {}",
+            task.title,
+            task.author,
+            task.is_private
+        );
+        Ok(())
+    }
+
+    // Creating a task whose body is kept off-chain, only its sha256 and uri are stored
+    // so the account skips the description's rent
+    pub fn create_task_hashed(
+        ctx: Context<CreateTaskHashed>,
+        title: String,
+        content_uri: String,
+        content_hash: [u8; 32],
+        due_date: Option<i64>,
+        priority: Priority,
+    ) -> Result<()> {
+        let task = &mut ctx.accounts.task;
+        let profile = &mut ctx.accounts.profile;
+        let clock = Clock::get()?;
+
+        validate_title(&title, &ctx.accounts.config)?;
+        validate_content_uri(&content_uri)?;
+        validate_due_date(due_date, clock.unix_timestamp)?;
+        collect_creation_fee(
+            &ctx.accounts.config,
+            &ctx.accounts.author,
+            &ctx.accounts.treasury,
+            &ctx.accounts.system_program,
+        )?;
+
+        task.init(
+            ctx.accounts.author.key(),
+            profile.task_count,
+            title,
+            content_uri,
+            due_date,
+            priority,
+            clock.unix_timestamp,
+        );
+
+        task.bump = ctx.bumps.task;
+        index_task(
+            ctx.accounts.task_index.as_mut(),
+            &ctx.accounts.author,
+            &ctx.accounts.system_program,
+            task.task_id,
+        )?;
+        task.content_hash = Some(content_hash);
+        profile.task_count = profile
+            .task_count
+            .checked_add(1)
+            .ok_or(ErrorTask::TaskCountOverflow)?;
+        require_open_slot(
+            profile,
+            &ctx.accounts.config,
+            ctx.accounts.subscription.as_ref(),
+        )?;
+        profile.record_created();
+        ctx.accounts.global_stats.record_created();
+        if let Some(board) = ctx.accounts.board.as_mut() {
+            task.board = Some(board.key());
+            task.visibility = Visibility::BoardOnly;
+            board.task_count = board
+                .task_count
+                .checked_add(1)
+                .ok_or(ErrorTask::TaskCountOverflow)?;
+        }
+
+        emit_cpi!(TaskCreated {
+            task: task.key(),
+            task_id: task.task_id,
+            author: task.author,
+            created_at: task.created_at,
+        });
+        msg!(
+            "Hashed task create, Id: {}, Title: {}, Author: {}, Uri: {}",
+            task.task_id,
+            task.title,
+            task.author,
+            task.description
+        );
+        Ok(())
+    }
+
+    // Pointing a hash-only task at new off-chain content
+    pub fn set_content(
+        ctx: Context<SetContent>,
+        content_uri: String,
+        content_hash: [u8; 32],
+    ) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+
+        require!(task.content_hash.is_some(), ErrorTask::ContentNotHashed);
+        validate_content_uri(&content_uri)?;
+
+        task.description = content_uri;
+        task.content_hash = Some(content_hash);
+        task.updated_at = Clock::get()?.unix_timestamp;
+        emit_cpi!(TaskUpdated {
+            task: task.key(),
+            task_id: task.task_id,
+            author: task.author,
+            updated_at: task.updated_at,
+        });
+        msg!(
+            "Task content updated, Title: {}, Author: {}, Uri: {}",
+            task.title,
+            task.author,
+            task.description
+        );
+        Ok(())
+    }
+
+    // Profiles created before the task index existed get theirs here, it starts empty
+    // and only tracks tasks created from now on
+    pub fn init_task_index(ctx: Context<InitTaskIndex>) -> Result<()> {
+        let task_index = &mut ctx.accounts.task_index;
+        task_index.author = ctx.accounts.author.key();
+        task_index.task_ids = Vec::new();
+        msg!("Task index created, Author: {}", task_index.author);
+        Ok(())
+    }
+
+    // Saving a reusable task shape, `{n}` in the title pattern becomes the instance number
+    pub fn create_template(
+        ctx: Context<CreateTemplate>,
+        title_pattern: String,
+        description: String,
+        priority: Priority,
+        tags: Vec<String>,
+    ) -> Result<()> {
+        validate_title(&title_pattern, &ctx.accounts.config)?;
+        require!(
+            title_pattern.len() <= Template::MAX_TITLE_PATTERN_LEN,
+            ErrorTask::TitleTooManyBytes
+        );
+        validate_description(&description, false, &ctx.accounts.config)?;
+        require!(
+            description.len() <= Template::MAX_DESCRIPTION_LEN,
+            ErrorTask::DescriptionTooManyBytes
+        );
+        require!(tags.len() <= 5, ErrorTask::TooManyTags);
+        for (i, tag) in tags.iter().enumerate() {
+            require!(tag.len() <= 20, ErrorTask::TagTooLong);
+            require!(!tag.trim().is_empty(), ErrorTask::TagIsEmpty);
+            require!(!tags[..i].contains(tag), ErrorTask::DuplicateTag);
+        }
+
+        let profile = &mut ctx.accounts.profile;
+        let template = &mut ctx.accounts.template;
+        template.author = ctx.accounts.author.key();
+        template.template_id = profile.template_count;
+        template.title_pattern = title_pattern;
+        template.description = description;
+        template.priority = priority;
+        template.tags = tags;
+        template.use_count = 0;
+        profile.template_count = profile
+            .template_count
+            .checked_add(1)
+            .ok_or(ErrorTask::TaskCountOverflow)?;
+        msg!(
+            "Template created, Id: {}, Title pattern: {}, Author: {}",
+            template.template_id,
+            template.title_pattern,
+            template.author
+        );
+        Ok(())
+    }
+
+    pub fn instantiate_template(
+        ctx: Context<InstantiateTemplate>,
+        due_date: Option<i64>,
+    ) -> Result<()> {
+        let template = &mut ctx.accounts.template;
+        let task = &mut ctx.accounts.task;
+        let profile = &mut ctx.accounts.profile;
+        let clock = Clock::get()?;
+
+        let title = template.title_for(template.use_count + 1);
+        validate_title(&title, &ctx.accounts.config)?;
+        validate_due_date(due_date, clock.unix_timestamp)?;
+        collect_creation_fee(
+            &ctx.accounts.config,
+            &ctx.accounts.author,
+            &ctx.accounts.treasury,
+            &ctx.accounts.system_program,
+        )?;
+
+        task.init(
+            ctx.accounts.author.key(),
+            profile.task_count,
+            title,
+            template.description.clone(),
+            due_date,
+            template.priority,
+            clock.unix_timestamp,
+        );
+        task.bump = ctx.bumps.task;
+        index_task(
+            ctx.accounts.task_index.as_mut(),
+            &ctx.accounts.author,
+            &ctx.accounts.system_program,
+            task.task_id,
+        )?;
+        task.tags = template.tags.clone();
+        template.use_count = template.use_count.saturating_add(1);
+        profile.task_count = profile
+            .task_count
+            .checked_add(1)
+            .ok_or(ErrorTask::TaskCountOverflow)?;
+        require_open_slot(
+            profile,
+            &ctx.accounts.config,
+            ctx.accounts.subscription.as_ref(),
+        )?;
+        profile.record_created();
+        ctx.accounts.global_stats.record_created();
+
+        emit_cpi!(TaskCreated {
+            task: task.key(),
+            task_id: task.task_id,
+            author: task.author,
+            created_at: task.created_at,
+        });
+        msg!(
+            "Task created from template, Id: {}, Template: {}, Title: {}, Author: {}",
+            task.task_id,
+            template.template_id,
+            task.title,
+            task.author
+        );
+        Ok(())
+    }
+
+    pub fn reorder_task(ctx: Context<ReorderTask>, sort_order: u32) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+
+        task.sort_order = sort_order;
+        msg!(
+            "Task reordered. Title: {}, Author: {}, Sort order: {}",
+            task.title,
+            task.author,
+            task.sort_order
+        );
+        Ok(())
+    }
+
+    pub fn pin_task(ctx: Context<PinTask>) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+        let profile = &mut ctx.accounts.profile;
+
+        require!(!task.is_pinned, ErrorTask::AlreadyPinned);
+        require!(
+            profile.pinned_count < UserProfile::MAX_PINNED,
+            ErrorTask::TooManyPinned
+        );
+
+        task.is_pinned = true;
+        profile.pinned_count += 1;
+        msg!(
+            "Task pinned. Title: {}, Author: {}",
+            task.title,
+            task.author
+        );
+        Ok(())
+    }
+
+    pub fn unpin_task(ctx: Context<UnpinTask>) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+        let profile = &mut ctx.accounts.profile;
+
+        require!(task.is_pinned, ErrorTask::NotPinned);
+
+        task.is_pinned = false;
+        profile.pinned_count = profile.pinned_count.saturating_sub(1);
+        msg!(
+            "Task unpinned. Title: {}, Author: {}",
+            task.title,
+            task.author
+        );
+        Ok(())
+    }
+
+    pub fn set_max_snoozes(ctx: Context<SetMaxSnoozes>, max_snoozes: u32) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.max_snoozes = max_snoozes;
+        msg!("Snooze limit updated, Max snoozes: {}", config.max_snoozes);
+        Ok(())
+    }
+
+    // Feed that stakes made from now on are slashed against, `None` goes back to the clock
+    pub fn set_time_oracle(ctx: Context<SetTimeOracle>, oracle: Option<Pubkey>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.time_oracle = oracle;
+        msg!("Time oracle updated, Oracle: {:?}", config.time_oracle);
+        Ok(())
+    }
+
+    // Restricting bounty currencies, e.g. to USDC, an empty list lifts the restriction
+    pub fn set_allowed_mints(ctx: Context<SetAllowedMints>, mints: Vec<Pubkey>) -> Result<()> {
+        require!(
+            mints.len() <= Config::MAX_ALLOWED_MINTS,
+            ErrorTask::TooManyAllowedMints
+        );
+        let config = &mut ctx.accounts.config;
+        config.allowed_mints = mints;
+        msg!("Bounty mints updated, Allowed: {:?}", config.allowed_mints);
+        Ok(())
+    }
+
+    // Pricing and limits of the paid tiers, indexed by `Tier`
+    pub fn set_subscription_tiers(
+        ctx: Context<SetSubscriptionTiers>,
+        tier_prices: [u64; 2],
+        tier_task_limits: [u64; 2],
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.tier_prices = tier_prices;
+        config.tier_task_limits = tier_task_limits;
+        msg!(
+            "Subscription tiers updated, Prices: {:?}, Task limits: {:?}",
+            config.tier_prices,
+            config.tier_task_limits
+        );
+        Ok(())
+    }
+
+    // Pushing the due date forward by `duration_secs`, counted from now when it's already past
+    pub fn snooze_task(ctx: Context<SnoozeTask>, duration_secs: i64) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+        let config = &ctx.accounts.config;
+        let clock = Clock::get()?;
+
+        require!(duration_secs > 0, ErrorTask::InvalidInterval);
+        require!(task.is_open(), ErrorTask::TaskNotOpen);
+        require!(
+            config.max_snoozes == 0 || task.snooze_count < config.max_snoozes,
+            ErrorTask::TooManySnoozes
+        );
+        let due_date = task.due_date.ok_or(ErrorTask::DueDateRequired)?;
+
+        task.due_date = Some(
+            due_date
+                .max(clock.unix_timestamp)
+                .checked_add(duration_secs)
+                .ok_or(ErrorTask::InvalidInterval)?,
+        );
+        task.snooze_count = task.snooze_count.saturating_add(1);
+        task.updated_at = clock.unix_timestamp;
+        msg!(
+            "Task snoozed. Title: {}, Author: {}, Due date: {:?}, Snoozes: {}",
+            task.title,
+            task.author,
+            task.due_date,
+            task.snooze_count
+        );
+        Ok(())
+    }
+
+    pub fn create_milestone(
+        ctx: Context<CreateMilestone>,
+        name: String,
+        target_date: i64,
+    ) -> Result<()> {
+        require!(name.len() <= 50, ErrorTask::MilestoneNameTooLong);
+        require!(!name.trim().is_empty(), ErrorTask::MilestoneNameIsEmpty);
+        validate_due_date(Some(target_date), Clock::get()?.unix_timestamp)?;
+
+        let profile = &mut ctx.accounts.profile;
+        let milestone = &mut ctx.accounts.milestone;
+        milestone.author = ctx.accounts.author.key();
+        milestone.milestone_id = profile.milestone_count;
+        milestone.name = name;
+        milestone.target_date = target_date;
+        milestone.total = 0;
+        milestone.completed = 0;
+        profile.milestone_count = profile
+            .milestone_count
+            .checked_add(1)
+            .ok_or(ErrorTask::TaskCountOverflow)?;
+        msg!(
+            "Milestone created, Id: {}, Name: {}, Author: {}",
+            milestone.milestone_id,
+            milestone.name,
+            milestone.author
+        );
+        Ok(())
+    }
+
+    pub fn link_milestone(ctx: Context<LinkMilestone>) -> Result<()> {
+        let events = EventAuthority {
+            info: ctx.accounts.event_authority.to_account_info(),
+            bump: ctx.bumps.event_authority,
+        };
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+        let milestone = &mut ctx.accounts.milestone;
+
+        require!(task.milestone.is_none(), ErrorTask::MilestoneLinked);
+
+        task.milestone = Some(milestone.key());
+        milestone.total = milestone.total.saturating_add(1);
+        if task.is_completed() {
+            milestone.completed = milestone.completed.saturating_add(1);
+        }
+        emit_milestone_progress(&events, milestone, task)?;
+        msg!(
+            "Task linked to milestone. Title: {}, Milestone: {}",
+            task.title,
+            milestone.name
+        );
+        Ok(())
+    }
+
+    pub fn unlink_milestone(ctx: Context<UnlinkMilestone>) -> Result<()> {
+        let events = EventAuthority {
+            info: ctx.accounts.event_authority.to_account_info(),
+            bump: ctx.bumps.event_authority,
+        };
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+        let milestone = &mut ctx.accounts.milestone;
+
+        task.milestone = None;
+        milestone.total = milestone.total.saturating_sub(1);
+        if task.is_completed() {
+            milestone.completed = milestone.completed.saturating_sub(1);
+        }
+        emit_milestone_progress(&events, milestone, task)?;
+        msg!(
+            "Task unlinked from milestone. Title: {}, Milestone: {}",
+            task.title,
+            milestone.name
+        );
+        Ok(())
+    }
+
+    pub fn create_sprint(
+        ctx: Context<CreateSprint>,
+        name: String,
+        start_at: i64,
+        end_at: i64,
+    ) -> Result<()> {
+        require!(name.len() <= 50, ErrorTask::SprintNameTooLong);
+        require!(!name.trim().is_empty(), ErrorTask::SprintNameIsEmpty);
+        require!(end_at > start_at, ErrorTask::InvalidSprintWindow);
+        validate_due_date(Some(end_at), Clock::get()?.unix_timestamp)?;
+
+        let profile = &mut ctx.accounts.profile;
+        let sprint = &mut ctx.accounts.sprint;
+        sprint.author = ctx.accounts.author.key();
+        sprint.sprint_id = profile.sprint_count;
+        sprint.name = name;
+        sprint.start_at = start_at;
+        sprint.end_at = end_at;
+        sprint.is_closed = false;
+        sprint.task_count = 0;
+        sprint.completed_count = 0;
+        profile.sprint_count = profile
+            .sprint_count
+            .checked_add(1)
+            .ok_or(ErrorTask::TaskCountOverflow)?;
+        msg!(
+            "Sprint created, Id: {}, Name: {}, Author: {}",
+            sprint.sprint_id,
+            sprint.name,
+            sprint.author
+        );
+        Ok(())
+    }
+
+    pub fn add_to_sprint(ctx: Context<AddToSprint>) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+        let sprint = &mut ctx.accounts.sprint;
+
+        require!(task.sprint.is_none(), ErrorTask::SprintLinked);
+        require!(
+            sprint.is_open(Clock::get()?.unix_timestamp),
+            ErrorTask::SprintNotOpen
+        );
+
+        task.sprint = Some(sprint.key());
+        sprint.task_count = sprint.task_count.saturating_add(1);
+        msg!(
+            "Task added to sprint. Title: {}, Sprint: {}",
+            task.title,
+            sprint.name
+        );
+        Ok(())
+    }
+
+    // Leaving an open sprint takes the task out of its count, a closed sprint keeps its stats
+    pub fn leave_sprint(ctx: Context<LeaveSprint>) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+        let sprint = &mut ctx.accounts.sprint;
+
+        task.sprint = None;
+        if !sprint.is_closed {
+            sprint.task_count = sprint.task_count.saturating_sub(1);
+        }
+        msg!(
+            "Task left sprint. Title: {}, Sprint: {}",
+            task.title,
+            sprint.name
+        );
+        Ok(())
+    }
+
+    // Every task in the sprint has to be passed in remaining_accounts so the completion
+    // count can be trusted, no task can join afterwards
+    pub fn close_sprint<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CloseSprint<'info>>,
+    ) -> Result<()> {
+        let sprint_key = ctx.accounts.sprint.key();
+        require!(
+            ctx.remaining_accounts.len() as u64 == ctx.accounts.sprint.task_count,
+            ErrorTask::SprintTasksMissing
+        );
+
+        let mut completed = 0u64;
+        for (i, account_info) in ctx.remaining_accounts.iter().enumerate() {
+            require!(
+                !ctx.remaining_accounts[..i]
+                    .iter()
+                    .any(|other| other.key == account_info.key),
+                ErrorTask::SprintTasksMissing
+            );
+            let task: Account<Task> = Account::try_from(account_info)?;
+            require!(task.sprint == Some(sprint_key), ErrorTask::SprintMismatch);
+            if task.is_completed() {
+                completed += 1;
+            }
+        }
+
+        let sprint = &mut ctx.accounts.sprint;
+        sprint.is_closed = true;
+        sprint.completed_count = completed;
+        msg!(
+            "Sprint closed. Name: {}, Completed: {}/{}",
+            sprint.name,
+            sprint.completed_count,
+            sprint.task_count
+        );
+        Ok(())
+    }
+
+    // Labels are seeded by name so an author can't end up with two of the same
+    pub fn create_label(ctx: Context<CreateLabel>, name: String, color: [u8; 3]) -> Result<()> {
+        require!(name.len() <= 32, ErrorTask::LabelNameTooLong);
+        require!(!name.trim().is_empty(), ErrorTask::LabelNameIsEmpty);
+
+        let label = &mut ctx.accounts.label;
+        label.author = ctx.accounts.author.key();
+        label.name = name;
+        label.color = color;
+        label.usage_count = 0;
+        msg!(
+            "Label created, Name: {}, Author: {}",
+            label.name,
+            label.author
+        );
+        Ok(())
+    }
+
+    pub fn delete_label(ctx: Context<DeleteLabel>) -> Result<()> {
+        let label = &ctx.accounts.label;
+        require!(label.usage_count == 0, ErrorTask::LabelInUse);
+        msg!(
+            "Label deleted, Name: {}, Author: {}",
+            label.name,
+            label.author
+        );
+        Ok(())
+    }
+
+    pub fn apply_label(ctx: Context<ApplyLabel>) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+        let label = &mut ctx.accounts.label;
+
+        require!(task.labels.len() < 5, ErrorTask::TooManyLabels);
+        require!(
+            !task.labels.contains(&label.key()),
+            ErrorTask::DuplicateLabel
+        );
+
+        task.labels.push(label.key());
+        label.usage_count = label.usage_count.saturating_add(1);
+        msg!(
+            "Label applied. Title: {}, Author: {}, Label: {}",
+            task.title,
+            task.author,
+            label.name
+        );
+        Ok(())
+    }
+
+    pub fn remove_label(ctx: Context<RemoveLabel>) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+        let label = &mut ctx.accounts.label;
+
+        let index = task
+            .labels
+            .iter()
+            .position(|l| *l == label.key())
+            .ok_or(ErrorTask::LabelNotFound)?;
+        task.labels.remove(index);
+        label.usage_count = label.usage_count.saturating_sub(1);
+        msg!(
+            "Label removed. Title: {}, Author: {}, Label: {}",
+            task.title,
+            task.author,
+            label.name
+        );
+        Ok(())
+    }
+
+    // Creating up to 10 tasks in one transaction, remaining_accounts holds the uninitialized
+    // task PDAs for the author's next ids in order, board tasks go through `create_task`
+    pub fn create_tasks<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateTasks<'info>>,
+        items: Vec<TaskInput>,
+    ) -> Result<()> {
+        require!(
+            !items.is_empty() && items.len() <= 10,
+            ErrorTask::InvalidBatchSize
+        );
+        require!(
+            ctx.remaining_accounts.len() == items.len(),
+            ErrorTask::InvalidBatchSize
+        );
+        let author = ctx.accounts.author.key();
+        let clock = Clock::get()?;
+        let rent = Rent::get()?;
+        let count = items.len();
+
+        for (item, account_info) in items.into_iter().zip(ctx.remaining_accounts.iter()) {
+            validate_title(&item.title, &ctx.accounts.config)?;
+            validate_description(&item.description, false, &ctx.accounts.config)?;
+            validate_due_date(item.due_date, clock.unix_timestamp)?;
+            require_open_slot(
+                &ctx.accounts.profile,
+                &ctx.accounts.config,
+                ctx.accounts.subscription.as_ref(),
+            )?;
+            collect_creation_fee(
+                &ctx.accounts.config,
+                &ctx.accounts.author,
+                &ctx.accounts.treasury,
+                &ctx.accounts.system_program,
+            )?;
+
+            let task_id = ctx.accounts.profile.task_count;
+            let task_id_bytes = task_id.to_le_bytes();
+            let (address, bump) = Pubkey::find_program_address(
+                &[b"task", author.as_ref(), task_id_bytes.as_ref()],
+                &crate::ID,
+            );
+            require_keys_eq!(address, account_info.key(), ErrorTask::InvalidTaskAccount);
+
+            create_pda(
+                &ctx.accounts.author,
+                account_info,
+                &ctx.accounts.system_program,
+                Task::space(&item.title, &item.description),
+                &rent,
+                &[b"task", author.as_ref(), task_id_bytes.as_ref(), &[bump]],
+            )?;
+            // A zeroed body deserializes as an empty task once the discriminator is in place
+            account_info.try_borrow_mut_data()?[..8].copy_from_slice(&Task::DISCRIMINATOR[..]);
+
+            let mut task: Account<Task> = Account::try_from(account_info)?;
+            task.init(
+                author,
+                task_id,
+                item.title,
+                item.description,
+                item.due_date,
+                item.priority,
+                clock.unix_timestamp,
+            );
+            task.bump = bump;
+            index_task(
+                ctx.accounts.task_index.as_mut(),
+                &ctx.accounts.author,
+                &ctx.accounts.system_program,
+                task_id,
+            )?;
+            let profile = &mut ctx.accounts.profile;
+            profile.task_count = profile
+                .task_count
+                .checked_add(1)
+                .ok_or(ErrorTask::TaskCountOverflow)?;
+            profile.record_created();
+            ctx.accounts.global_stats.record_created();
+            emit_cpi!(TaskCreated {
+                task: task.key(),
+                task_id: task.task_id,
+                author,
+                created_at: task.created_at,
+            });
+            task.exit(&crate::ID)?;
+        }
+
+        msg!("Tasks created. Author: {}, Count: {}", author, count);
+        Ok(())
+    }
+
+    // Closing every completed task passed in remaining_accounts back to the author, tasks
+    // that still hold funds or links (board, milestone, sprint, labels, a parent or
+    // dependencies) go through `delete_task`
+    pub fn sweep_completed<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SweepCompleted<'info>>,
+    ) -> Result<()> {
+        let author = ctx.accounts.author.key();
+        let clock = Clock::get()?;
+
+        for account_info in ctx.remaining_accounts.iter() {
+            require!(account_info.is_writable, ErrorTask::AccountNotWritable);
+            let task: Account<Task> = Account::try_from(account_info)?;
+
+            require_keys_eq!(task.author, author, ErrorTask::Unauthorized);
+            require!(task.is_completed(), ErrorTask::TaskNotCompleted);
+            require!(
+                !task.has_bounty && !task.has_token_bounty && !task.has_stake,
+                ErrorTask::TaskHasFunds
+            );
+            require!(task.board.is_none(), ErrorTask::BoardMismatch);
+            require!(task.milestone.is_none(), ErrorTask::MilestoneLinked);
+            require!(task.sprint.is_none(), ErrorTask::SprintLinked);
+            require!(task.labels.is_empty(), ErrorTask::LabelsApplied);
+            require!(
+                task.parent.is_none() && task.blocked_by.is_empty(),
+                ErrorTask::DependenciesLinked
+            );
+
+            let profile = &mut ctx.accounts.profile;
+            profile.record_deleted(false);
+            if task.is_pinned {
+                profile.pinned_count = profile.pinned_count.saturating_sub(1);
+            }
+            if let Some(task_index) = ctx.accounts.task_index.as_mut() {
+                task_index.remove(task.task_id);
+            }
+            emit_cpi!(TaskDeleted {
+                task: task.key(),
+                task_id: task.task_id,
+                author,
+                deleted_at: clock.unix_timestamp,
+            });
+            task.close(ctx.accounts.author.to_account_info())?;
+        }
+
+        msg!(
+            "Completed tasks swept. Author: {}, Count: {}",
+            author,
+            ctx.remaining_accounts.len()
+        );
+        Ok(())
+    }
+
+    pub fn trash_task(ctx: Context<TrashTask>) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+
+        require!(task.deleted_at.is_none(), ErrorTask::AlreadyTrashed);
+        require!(task.timer_started_at.is_none(), ErrorTask::TimerRunning);
+
+        task.deleted_at = Some(Clock::get()?.unix_timestamp);
+        // A trashed task no longer counts against the open task limit
+        if task.is_open() {
+            ctx.accounts.profile.record_trashed();
+        }
+        msg!(
+            "Task trashed. Title: {}, Author: {}",
+            task.title,
+            task.author
+        );
+        Ok(())
+    }
+
+    pub fn restore_task(ctx: Context<RestoreTask>) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+
+        require!(task.deleted_at.is_some(), ErrorTask::NotTrashed);
+        if task.is_open() {
+            require_open_slot(
+                &ctx.accounts.profile,
+                &ctx.accounts.config,
+                ctx.accounts.subscription.as_ref(),
+            )?;
+            ctx.accounts.profile.record_restored();
+        }
+
+        task.deleted_at = None;
+        msg!(
+            "Task restored. Title: {}, Author: {}",
+            task.title,
+            task.author
+        );
+        Ok(())
+    }
+
+    // Anyone can purge a task that's been in the trash past the grace window, the rent
+    // goes back to the author. Its parent and `blocked_by` tasks come in as remaining accounts
+    pub fn purge_task<'info>(ctx: Context<'_, '_, 'info, 'info, PurgeTask<'info>>) -> Result<()> {
+        let task = &ctx.accounts.task;
+        let deleted_at = task.deleted_at.ok_or(ErrorTask::NotTrashed)?;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            now > deleted_at.saturating_add(Task::TRASH_GRACE),
+            ErrorTask::TrashGraceNotOver
+        );
+        require!(
+            !task.has_bounty && !task.has_token_bounty && !task.has_stake,
+            ErrorTask::TaskHasFunds
+        );
+        require!(task.milestone.is_none(), ErrorTask::MilestoneLinked);
+        require!(task.sprint.is_none(), ErrorTask::SprintLinked);
+        require!(task.labels.is_empty(), ErrorTask::LabelsApplied);
+        count_against_dependencies(task, ctx.remaining_accounts, false)?;
+
+        if task.board.is_some() {
+            let board = ctx
+                .accounts
+                .board
+                .as_mut()
+                .ok_or(ErrorTask::BoardMismatch)?;
+            board.task_count = board.task_count.saturating_sub(1);
+        }
+        let profile = &mut ctx.accounts.profile;
+        // Its open slot was already given back when it was trashed
+        profile.record_deleted(false);
+        if task.is_pinned {
+            profile.pinned_count = profile.pinned_count.saturating_sub(1);
+        }
+        if let Some(task_index) = ctx.accounts.task_index.as_mut() {
+            task_index.remove(task.task_id);
+        }
+        emit_cpi!(TaskDeleted {
+            task: task.key(),
+            task_id: task.task_id,
+            author: task.author,
+            deleted_at: now,
+        });
+        msg!(
+            "Task purged. Title: {}, Author: {}",
+            task.title,
+            task.author
+        );
+        Ok(())
+    }
+
+    // Requiring (or no longer requiring) a reviewer's sign-off before the task counts as done
+    pub fn set_reviewer(ctx: Context<SetReviewer>, reviewer: Option<Pubkey>) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+        require!(
+            task.status != TaskStatus::PendingReview,
+            ErrorTask::AwaitingReview
+        );
+        require!(!task.is_completed(), ErrorTask::AlreadyCompleted);
+        require!(
+            reviewer.is_none() || task.required_approvals == 0,
+            ErrorTask::ReviewerAndApprovals
+        );
+        task.reviewer = reviewer;
+        task.updated_at = Clock::get()?.unix_timestamp;
+        msg!(
+            "Task reviewer set. Title: {}, Author: {}, Reviewer: {:?}",
+            task.title,
+            task.author,
+            task.reviewer
+        );
+        Ok(())
+    }
+
+    // The dependencies and subtasks come in remaining_accounts again, any of them may have
+    // been reopened since the task was submitted
+    pub fn approve_completion<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ApproveCompletion<'info>>,
+    ) -> Result<()> {
+        let events = EventAuthority {
+            info: ctx.accounts.event_authority.to_account_info(),
+            bump: ctx.bumps.event_authority,
+        };
+        require!(
+            ctx.accounts.task.status == TaskStatus::PendingReview,
+            ErrorTask::NotPendingReview
+        );
+        require_dependencies_done(&ctx.accounts.task, ctx.remaining_accounts)?;
+        let clock = Clock::get()?;
+        finish_completion(
+            &events,
+            &mut ctx.accounts.task,
+            &mut ctx.accounts.profile,
+            &mut ctx.accounts.global_stats,
+            ctx.accounts.milestone.as_mut(),
+            ctx.accounts.stake.as_ref(),
+            ctx.accounts.author.as_ref(),
+            ctx.accounts.reward_pool.as_mut(),
+            ctx.accounts.reward_tracker.as_mut(),
+            clock.unix_timestamp,
+        )?;
+        let task = &ctx.accounts.task;
+        log_activity(
+            ctx.accounts.activity_log.as_mut(),
+            task.board,
+            ctx.accounts.reviewer.key(),
+            Activity::Completed,
+            task.key(),
+        )?;
+        notify(&events, task.author, NotificationKind::Approved, task)?;
+        emit_cpi!(TaskCompleted {
+            task: task.key(),
+            task_id: task.task_id,
+            author: task.author,
+            completed_by: ctx.accounts.reviewer.key(),
+            completed_at: clock.unix_timestamp,
+        });
+        msg!(
+            "Task completion approved. Title: {}, Author: {}, Reviewer: {}",
+            task.title,
+            task.author,
+            ctx.accounts.reviewer.key()
+        );
+        Ok(())
+    }
+
+    // Bouncing the task back to InProgress, the note is only logged
+    pub fn request_changes(ctx: Context<RequestChanges>, note: String) -> Result<()> {
+        let events = EventAuthority {
+            info: ctx.accounts.event_authority.to_account_info(),
+            bump: ctx.bumps.event_authority,
+        };
+        require!(note.len() <= 200, ErrorTask::ReviewNoteTooLong);
+        let task = &mut ctx.accounts.task;
+        require!(
+            task.status == TaskStatus::PendingReview,
+            ErrorTask::NotPendingReview
+        );
+        let clock = Clock::get()?;
+        task.status = TaskStatus::InProgress;
+        task.updated_at = clock.unix_timestamp;
+        log_activity(
+            ctx.accounts.activity_log.as_mut(),
+            task.board,
+            ctx.accounts.reviewer.key(),
+            Activity::StatusChanged,
+            task.key(),
+        )?;
+        notify(
+            &events,
+            task.author,
+            NotificationKind::ChangesRequested,
+            task,
+        )?;
+        emit_watched_status(&events, task)?;
+        emit_cpi!(ChangesRequested {
+            task: task.key(),
+            task_id: task.task_id,
+            reviewer: ctx.accounts.reviewer.key(),
+            timestamp: clock.unix_timestamp,
+        });
+        msg!(
+            "Changes requested. Title: {}, Author: {}, Note: {}",
+            task.title,
+            task.author,
+            note
+        );
+        Ok(())
+    }
+
+    // Team tasks on a board can require `required` distinct members to approve, approvals
+    // collected so far are dropped when the threshold changes
+    pub fn set_required_approvals(ctx: Context<SetRequiredApprovals>, required: u8) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+        require!(task.board.is_some(), ErrorTask::BoardMismatch);
+        require!(!task.is_completed(), ErrorTask::AlreadyCompleted);
+        require!(task.reviewer.is_none(), ErrorTask::ReviewerAndApprovals);
+        require!(
+            required as usize
+                <= ctx
+                    .accounts
+                    .board
+                    .as_ref()
+                    .map_or(0, |b| b.members.len() + 1),
+            ErrorTask::TooManyApprovals
+        );
+        task.required_approvals = required;
+        task.approvals.clear();
+        task.updated_at = Clock::get()?.unix_timestamp;
+        msg!(
+            "Task approvals required. Title: {}, Author: {}, Required: {}",
+            task.title,
+            task.author,
+            required
+        );
+        Ok(())
+    }
+
+    // Each board member approves once, the approval that reaches the threshold completes the
+    // task. Approvers who have since left the board are dropped, and the dependencies and
+    // subtasks are checked from remaining_accounts like `complete_task` does
+    pub fn approve_task<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ApproveTask<'info>>,
+    ) -> Result<()> {
+        let events = EventAuthority {
+            info: ctx.accounts.event_authority.to_account_info(),
+            bump: ctx.bumps.event_authority,
+        };
+        let approver = ctx.accounts.approver.key();
+        let board = &ctx.accounts.board;
+        let task = &mut ctx.accounts.task;
+        require!(task.required_approvals > 0, ErrorTask::ApprovalsNotRequired);
+        require!(task.is_open(), ErrorTask::AlreadyCompleted);
+        require!(
+            task.status.can_transition_to(TaskStatus::Done),
+            ErrorTask::InvalidStatusTransition
+        );
+        require!(
+            board.role_of(&approver).is_some(),
+            ErrorTask::NotBoardMember
+        );
+        require!(
+            !task.approvals.contains(&approver),
+            ErrorTask::AlreadyApproved
+        );
+        task.approvals.retain(|a| board.role_of(a).is_some());
+        task.approvals.push(approver);
+        let approvals = task.approvals.len() as u8;
+        notify(&events, task.author, NotificationKind::Approved, task)?;
+        emit_cpi!(TaskApproved {
+            task: task.key(),
+            task_id: task.task_id,
+            approver,
+            approvals,
+            required: task.required_approvals,
+        });
+        msg!(
+            "Task approved. Title: {}, Approver: {}, Approvals: {}/{}",
+            task.title,
+            approver,
+            approvals,
+            task.required_approvals
+        );
+        if approvals < task.required_approvals {
+            return Ok(());
+        }
+        require_dependencies_done(&ctx.accounts.task, ctx.remaining_accounts)?;
+
+        let clock = Clock::get()?;
+        finish_completion(
+            &events,
+            &mut ctx.accounts.task,
+            &mut ctx.accounts.profile,
+            &mut ctx.accounts.global_stats,
+            ctx.accounts.milestone.as_mut(),
+            ctx.accounts.stake.as_ref(),
+            ctx.accounts.author.as_ref(),
+            ctx.accounts.reward_pool.as_mut(),
+            ctx.accounts.reward_tracker.as_mut(),
+            clock.unix_timestamp,
+        )?;
+        let task = &ctx.accounts.task;
+        log_activity(
+            ctx.accounts.activity_log.as_mut(),
+            task.board,
+            approver,
+            Activity::Completed,
+            task.key(),
+        )?;
+        emit_cpi!(TaskCompleted {
+            task: task.key(),
+            task_id: task.task_id,
+            author: task.author,
+            completed_by: approver,
+            completed_at: clock.unix_timestamp,
+        });
+        msg!(
+            "Task is marked complete. Title: {}, Author: {}",
+            task.title,
+            task.author
+        );
+        Ok(())
+    }
+
+    // Handing control of the task to another key, e.g. a Squads vault that signs via CPI.
+    // The task keeps its address and stays on the author's profile
+    pub fn set_task_authority(ctx: Context<SetTaskAuthority>, new_authority: Pubkey) -> Result<()> {
+        let task = &mut ctx.accounts.task;
+        let previous = task.authority;
+        task.authority = new_authority;
+        task.updated_at = Clock::get()?.unix_timestamp;
+        emit_cpi!(TaskAuthorityChanged {
+            task: task.key(),
+            task_id: task.task_id,
+            previous,
+            authority: new_authority,
+        });
+        msg!(
+            "Task authority changed. Title: {}, Author: {}, Authority: {}",
+            task.title,
+            task.author,
+            task.authority
+        );
+        Ok(())
+    }
+
+    // Letting a hot session key call the instructions in `actions` (a `Delegate` bit mask)
+    // until `expires_at`, revoke it to change the grant
+    pub fn add_delegate(
+        ctx: Context<AddDelegate>,
+        session_key: Pubkey,
+        expires_at: i64,
+        actions: u8,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        require!(
+            expires_at > clock.unix_timestamp,
+            ErrorTask::DelegateExpired
+        );
+        require!(
+            actions != 0 && actions & !Delegate::ALL == 0,
+            ErrorTask::InvalidDelegateActions
+        );
+        let delegate = &mut ctx.accounts.delegate;
+        delegate.owner = ctx.accounts.owner.key();
+        delegate.session_key = session_key;
+        delegate.expires_at = expires_at;
+        delegate.actions = actions;
+        msg!(
+            "Delegate added. Owner: {}, Session key: {}, Expires at: {}",
+            delegate.owner,
+            session_key,
+            expires_at
+        );
+        Ok(())
+    }
+
+    pub fn revoke_delegate(ctx: Context<RevokeDelegate>) -> Result<()> {
+        msg!(
+            "Delegate revoked. Owner: {}, Session key: {}",
+            ctx.accounts.owner.key(),
+            ctx.accounts.delegate.session_key
+        );
+        Ok(())
+    }
+
+    // Sending the task's author a tip with a short message, in lamports or, when the token
+    // accounts are passed, in any SPL / Token-2022 mint
+    pub fn tip_task(ctx: Context<TipTask>, amount: u64, message: String) -> Result<()> {
+        require!(amount > 0, ErrorTask::InvalidAmount);
+        require!(message.len() <= 200, ErrorTask::TipMessageTooLong);
+
+        let accounts = &ctx.accounts;
+        let mint = match (
+            accounts.mint.as_ref(),
+            accounts.tipper_token_account.as_ref(),
+            accounts.author_token_account.as_ref(),
+            accounts.token_program.as_ref(),
+        ) {
+            (None, None, None, None) => {
+                system_program::transfer(
+                    CpiContext::new(
+                        accounts.system_program.to_account_info(),
+                        system_program::Transfer {
+                            from: accounts.tipper.to_account_info(),
+                            to: accounts.author.to_account_info(),
+                        },
+                    ),
+                    amount,
+                )?;
+                None
+            }
+            (Some(mint), Some(from), Some(to), Some(token_program)) => {
+                require_keys_eq!(
+                    to.owner,
+                    accounts.task.author,
+                    ErrorTask::TipAccountMismatch
+                );
+                require_keys_eq!(to.mint, mint.key(), ErrorTask::TipAccountMismatch);
+                token_interface::transfer_checked(
+                    CpiContext::new(
+                        token_program.to_account_info(),
+                        token_interface::TransferChecked {
+                            from: from.to_account_info(),
+                            mint: mint.to_account_info(),
+                            to: to.to_account_info(),
+                            authority: accounts.tipper.to_account_info(),
+                        },
+                    ),
+                    amount,
+                    mint.decimals,
+                )?;
+                Some(mint.key())
+            }
+            _ => return err!(ErrorTask::TipAccountMismatch),
+        };
+
+        emit_cpi!(TaskTipped {
+            task: accounts.task.key(),
+            task_id: accounts.task.task_id,
+            author: accounts.task.author,
+            tipper: accounts.tipper.key(),
+            mint,
+            amount,
+            message: message.clone(),
+        });
+        msg!(
+            "Task tipped. Title: {}, Author: {}, Tipper: {}, Amount: {}, Message: {}",
+            accounts.task.title,
+            accounts.task.author,
+            accounts.tipper.key(),
+            amount,
+            message
+        );
+        Ok(())
+    }
+
+    // Buying `periods` of a paid tier, the lamports go to the treasury
+    pub fn purchase_subscription(
+        ctx: Context<PurchaseSubscription>,
+        tier: Tier,
+        periods: u16,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        charge_subscription(
+            &ctx.accounts.config,
+            tier,
+            periods,
+            &ctx.accounts.author,
+            &ctx.accounts.treasury,
+            &ctx.accounts.system_program,
+        )?;
+        let subscription = &mut ctx.accounts.subscription;
+        subscription.author = ctx.accounts.author.key();
+        subscription.tier = tier;
+        subscription.expires_at = now + Subscription::PERIOD * periods as i64;
+        msg!(
+            "Subscription purchased, Author: {}, Tier: {:?}, Expires at: {}",
+            subscription.author,
+            subscription.tier,
+            subscription.expires_at
+        );
+        Ok(())
+    }
+
+    // Extending the subscription from its current expiry, or from now once it has lapsed.
+    // The tier can only change after it lapses
+    pub fn renew_subscription(
+        ctx: Context<RenewSubscription>,
+        tier: Tier,
+        periods: u16,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let active = ctx.accounts.subscription.expires_at > now;
+        require!(
+            !active || ctx.accounts.subscription.tier == tier,
+            ErrorTask::SubscriptionActive
+        );
+        charge_subscription(
+            &ctx.accounts.config,
+            tier,
+            periods,
+            &ctx.accounts.author,
+            &ctx.accounts.treasury,
+            &ctx.accounts.system_program,
+        )?;
+        let subscription = &mut ctx.accounts.subscription;
+        subscription.tier = tier;
+        subscription.expires_at =
+            subscription.expires_at.max(now) + Subscription::PERIOD * periods as i64;
+        msg!(
+            "Subscription renewed, Author: {}, Tier: {:?}, Expires at: {}",
+            subscription.author,
+            subscription.tier,
+            subscription.expires_at
+        );
+        Ok(())
+    }
+
+    // Handing admin rights over the board to a DAO. The owner sets the first authority, from
+    // then on only that authority can change or clear it, and owner-only instructions like
+    // `close_board` need its signature too
+    pub fn set_board_authority(
+        ctx: Context<SetBoardAuthority>,
+        authority: Option<Pubkey>,
+    ) -> Result<()> {
+        let board = &mut ctx.accounts.board;
+        board.authority = authority;
+        msg!(
+            "Board authority set. Board: {}, Authority: {:?}",
+            board.name,
+            board.authority
+        );
+        Ok(())
+    }
+
+    // Renaming the board, e.g. by a governance proposal
+    pub fn rename_board(ctx: Context<RenameBoard>, name: String) -> Result<()> {
+        require!(name.len() <= 50, ErrorTask::BoardNameTooLong);
+        require!(!name.trim().is_empty(), ErrorTask::BoardNameIsEmpty);
+        let board = &mut ctx.accounts.board;
+        msg!(
+            "Board renamed, Old name: {}, New name: {}, Owner: {}",
+            board.name,
+            name,
+            board.owner
+        );
+        board.name = name;
+        Ok(())
+    }
+
+    pub fn start_timer(ctx: Context<StartTimer>) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+        let profile = &mut ctx.accounts.profile;
+
+        require!(task.is_open(), ErrorTask::TaskNotOpen);
+        require!(profile.active_timer.is_none(), ErrorTask::TimerRunning);
+
+        let now = Clock::get()?.unix_timestamp;
+        task.timer_started_at = Some(now);
+        profile.active_timer = Some(task.key());
+        msg!(
+            "Timer started. Title: {}, Author: {}, Started at: {}",
+            task.title,
+            task.author,
+            now
+        );
+        Ok(())
+    }
+
+    pub fn stop_timer(ctx: Context<StopTimer>) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+        let now = Clock::get()?.unix_timestamp;
+
+        let elapsed = task.stop_timer(now).ok_or(ErrorTask::TimerNotRunning)?;
+        ctx.accounts.profile.active_timer = None;
+        task.updated_at = now;
+        msg!(
+            "Timer stopped. Title: {}, Author: {}, Elapsed: {}s, Total: {}s",
+            task.title,
+            task.author,
+            elapsed,
+            task.total_seconds_spent
+        );
+        Ok(())
+    }
+
+    // Logging time spent on the task, the authority and an accepted assignee can both log
+    pub fn log_work(ctx: Context<LogWork>, duration_secs: u64, note: String) -> Result<()> {
+        require!(duration_secs > 0, ErrorTask::InvalidInterval);
+        require!(note.len() <= 200, ErrorTask::WorkNoteTooLong);
+
+        let task = &mut ctx.accounts.task;
+        let work_log = &mut ctx.accounts.work_log;
+        work_log.task = task.key();
+        work_log.author = ctx.accounts.author.key();
+        work_log.index = task.work_log_count;
+        work_log.duration_secs = duration_secs;
+        work_log.note = note;
+        work_log.logged_at = Clock::get()?.unix_timestamp;
+        task.work_log_count = task.work_log_count.saturating_add(1);
+        msg!(
+            "Work logged. Title: {}, Author: {}, Duration: {}s",
+            task.title,
+            work_log.author,
+            duration_secs
+        );
+        Ok(())
+    }
+
+    // Only whoever logged the entry can delete it, the task may be gone already
+    pub fn delete_work_log(ctx: Context<DeleteWorkLog>) -> Result<()> {
+        let work_log = &ctx.accounts.work_log;
+        msg!(
+            "Work log deleted. Task: {}, Author: {}, Index: {}",
+            work_log.task,
+            work_log.author,
+            work_log.index
+        );
+        Ok(())
+    }
+
+    // Counting a finished pomodoro against the task and the author's day, the session
+    // itself is timed by the frontend
+    pub fn record_pomodoro(ctx: Context<RecordPomodoro>) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+        let profile = &mut ctx.accounts.profile;
+
+        require!(task.is_open(), ErrorTask::TaskNotOpen);
+
+        task.pomodoros_completed = task.pomodoros_completed.saturating_add(1);
+        profile.record_pomodoro(Clock::get()?.unix_timestamp);
+        msg!(
+            "Pomodoro recorded. Title: {}, Author: {}, Task total: {}, Today: {}",
+            task.title,
+            task.author,
+            task.pomodoros_completed,
+            profile.pomodoros_today
+        );
+        Ok(())
+    }
+
+    pub fn set_estimate(ctx: Context<SetEstimate>, estimate_seconds: Option<u64>) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+        require!(!task.is_completed(), ErrorTask::AlreadyCompleted);
+        validate_estimate(estimate_seconds)?;
+        task.estimate_seconds = estimate_seconds;
+        task.updated_at = Clock::get()?.unix_timestamp;
+        msg!(
+            "Task estimate set. Title: {}, Author: {}, Estimate: {:?}",
+            task.title,
+            task.author,
+            task.estimate_seconds
+        );
+        Ok(())
+    }
+
+    pub fn set_category(ctx: Context<SetCategory>, category: Category) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+        task.category = category;
+        task.updated_at = Clock::get()?.unix_timestamp;
+        msg!(
+            "Task category changed to {:?}. Title: {}, Author: {}",
+            task.category,
+            task.title,
+            task.author
+        );
+        Ok(())
+    }
+
+    // Inserting or overwriting the entry under `key`
+    pub fn set_metadata_entry(
+        ctx: Context<SetMetadataEntry>,
+        key: String,
+        value: String,
+    ) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+
+        require!(key.len() <= 32, ErrorTask::MetadataKeyTooLong);
+        require!(!key.trim().is_empty(), ErrorTask::MetadataKeyIsEmpty);
+        require!(value.len() <= 64, ErrorTask::MetadataValueTooLong);
+
+        match task.metadata.iter_mut().find(|entry| entry.key == key) {
+            Some(entry) => entry.value = value,
+            None => {
+                require!(task.metadata.len() < 5, ErrorTask::TooManyMetadataEntries);
+                task.metadata.push(MetadataEntry { key, value });
+            }
+        }
+        task.updated_at = Clock::get()?.unix_timestamp;
+        msg!(
+            "Metadata set. Title: {}, Author: {}, Entries: {}",
+            task.title,
+            task.author,
+            task.metadata.len()
+        );
+        Ok(())
+    }
+
+    pub fn remove_metadata_entry(ctx: Context<RemoveMetadataEntry>, key: String) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+
+        let index = task
+            .metadata
+            .iter()
+            .position(|entry| entry.key == key)
+            .ok_or(ErrorTask::MetadataNotFound)?;
+        task.metadata.remove(index);
+        task.updated_at = Clock::get()?.unix_timestamp;
+        msg!(
+            "Metadata removed. Title: {}, Author: {}, Key: {}",
+            task.title,
+            task.author,
+            key
+        );
+        Ok(())
+    }
+
+    pub fn init_reactions(ctx: Context<InitReactions>) -> Result<()> {
+        ctx.accounts.reactions.task = ctx.accounts.task.key();
+        msg!("Reactions created, Task: {}", ctx.accounts.task.key());
+        Ok(())
+    }
+
+    // Reacts on the task, or on one of its comments when `comment` is passed. A wallet can
+    // use each emoji once per target
+    pub fn react(ctx: Context<React>, emoji_code: u32) -> Result<()> {
+        let task = &ctx.accounts.task;
+        let reactor = ctx.accounts.reactor.key();
+        require_visible_to(task, ctx.accounts.board.as_ref(), &reactor)?;
+
+        let comment_id = ctx.accounts.comment.as_ref().map(|c| c.comment_id);
+        let reaction = Reaction {
+            reactor,
+            emoji_code,
+            comment_id,
+        };
+        let reactions = &mut ctx.accounts.reactions;
+        require!(
+            !reactions.entries.contains(&reaction),
+            ErrorTask::AlreadyReacted
+        );
+        require!(
+            reactions.entries.len() < Reactions::CAPACITY,
+            ErrorTask::TooManyReactions
+        );
+        reactions.entries.push(reaction);
+
+        emit_cpi!(TaskReacted {
+            task: task.key(),
+            task_id: task.task_id,
+            reactor,
+            emoji_code,
+            comment_id,
+        });
+        Ok(())
+    }
+
+    pub fn unreact(ctx: Context<React>, emoji_code: u32) -> Result<()> {
+        let reaction = Reaction {
+            reactor: ctx.accounts.reactor.key(),
+            emoji_code,
+            comment_id: ctx.accounts.comment.as_ref().map(|c| c.comment_id),
+        };
+        let reactions = &mut ctx.accounts.reactions;
+        let position = reactions
+            .entries
+            .iter()
+            .position(|r| *r == reaction)
+            .ok_or(ErrorTask::ReactionNotFound)?;
+        reactions.entries.swap_remove(position);
+        msg!(
+            "Reaction removed, Task: {}, Reactor: {}",
+            reactions.task,
+            reaction.reactor
+        );
+        Ok(())
+    }
+
+    pub fn watch_task(ctx: Context<WatchTask>) -> Result<()> {
+        let task = &mut ctx.accounts.task;
+        let watcher = &mut ctx.accounts.watcher;
+
+        watcher.task = task.key();
+        watcher.wallet = ctx.accounts.wallet.key();
+        watcher.created_at = Clock::get()?.unix_timestamp;
+        task.watcher_count = task.watcher_count.saturating_add(1);
+        msg!(
+            "Watching task. Task: {}, Wallet: {}",
+            task.key(),
+            watcher.wallet
+        );
+        Ok(())
+    }
+
+    pub fn unwatch_task(ctx: Context<UnwatchTask>) -> Result<()> {
+        let task = &mut ctx.accounts.task;
+        task.watcher_count = task.watcher_count.saturating_sub(1);
+        msg!(
+            "Stopped watching task. Task: {}, Wallet: {}",
+            task.key(),
+            ctx.accounts.wallet.key()
+        );
+        Ok(())
+    }
+
+    pub fn set_visibility(ctx: Context<SetVisibility>, visibility: Visibility) -> Result<()> {
+        require_board_editor(
+            &ctx.accounts.task,
+            ctx.accounts.board.as_ref(),
+            &ctx.accounts.author.key(),
+        )?;
+        let task = &mut ctx.accounts.task;
+        require!(
+            visibility != Visibility::BoardOnly || task.board.is_some(),
+            ErrorTask::BoardMismatch
+        );
+        task.visibility = visibility;
+        task.updated_at = Clock::get()?.unix_timestamp;
+        msg!(
+            "Task visibility changed to {:?}. Title: {}, Author: {}",
+            task.visibility,
+            task.title,
+            task.author
+        );
+        Ok(())
+    }
+
+    // Compressed tasks live as leaves of an account-compression tree instead of one account
+    // each. The tree account is allocated by the client beforehand, the `TaskTree` PDA becomes
+    // its authority
+    pub fn init_task_tree(
+        ctx: Context<InitTaskTree>,
+        max_depth: u32,
+        max_buffer_size: u32,
+    ) -> Result<()> {
+        let merkle_tree = ctx.accounts.merkle_tree.key();
+        let bump = [ctx.bumps.task_tree];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"task_tree", merkle_tree.as_ref(), &bump]];
+
+        spl_account_compression::cpi::init_empty_merkle_tree(
+            CpiContext::new_with_signer(
+                ctx.accounts.compression_program.to_account_info(),
+                InitTree {
+                    merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+                    authority: ctx.accounts.task_tree.to_account_info(),
+                    noop: ctx.accounts.log_wrapper.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            max_depth,
+            max_buffer_size,
+        )?;
+
+        let task_tree = &mut ctx.accounts.task_tree;
+        task_tree.author = ctx.accounts.author.key();
+        task_tree.merkle_tree = merkle_tree;
+        task_tree.leaf_count = 0;
+        task_tree.bump = ctx.bumps.task_tree;
+        msg!(
+            "Task tree created, Author: {}, Tree: {}",
+            task_tree.author,
+            merkle_tree
+        );
+        Ok(())
+    }
+
+    pub fn create_compressed_task(
+        ctx: Context<CreateCompressedTask>,
+        title: String,
+        priority: Priority,
+    ) -> Result<()> {
+        validate_title(&title, &ctx.accounts.config)?;
+        require_open_slot(
+            &ctx.accounts.profile,
+            &ctx.accounts.config,
+            ctx.accounts.subscription.as_ref(),
+        )?;
+        collect_creation_fee(
+            &ctx.accounts.config,
+            &ctx.accounts.author,
+            &ctx.accounts.treasury,
+            &ctx.accounts.system_program,
+        )?;
+        let now = Clock::get()?.unix_timestamp;
+        let task_tree = &ctx.accounts.task_tree;
+        let task = CompressedTask {
+            author: task_tree.author,
+            index: u32::try_from(task_tree.leaf_count).map_err(|_| ErrorTask::TaskTreeFull)?,
+            title,
+            priority,
+            status: TaskStatus::Todo,
+            created_at: now,
+            updated_at: now,
+            completed_at: None,
+        };
+        let leaf = task.leaf()?;
+
+        let merkle_tree = task_tree.merkle_tree;
+        let bump = [task_tree.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"task_tree", merkle_tree.as_ref(), &bump]];
+        spl_account_compression::cpi::append(
+            CpiContext::new_with_signer(
+                ctx.accounts.compression_program.to_account_info(),
+                ModifyTree {
+                    merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+                    authority: ctx.accounts.task_tree.to_account_info(),
+                    noop: ctx.accounts.log_wrapper.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            leaf,
+        )?;
+
+        let task_tree = &mut ctx.accounts.task_tree;
+        task_tree.leaf_count = task_tree.leaf_count.saturating_add(1);
+        ctx.accounts.profile.record_created();
+        emit_cpi!(CompressedTaskChanged {
+            merkle_tree,
+            leaf,
+            task,
+        });
+        Ok(())
+    }
+
+    // `previous` is the task as it is stored now, its proof goes in the remaining accounts
+    pub fn update_compressed_task<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ModifyTaskTree<'info>>,
+        root: [u8; 32],
+        previous: CompressedTask,
+        title: String,
+        priority: Priority,
+    ) -> Result<()> {
+        validate_title(&title, &ctx.accounts.config)?;
+        require!(
+            previous.status != TaskStatus::Done,
+            ErrorTask::AlreadyCompleted
+        );
+        let mut task = previous.clone();
+        task.title = title;
+        task.priority = priority;
+        task.updated_at = Clock::get()?.unix_timestamp;
+        replace_compressed_task(&ctx, root, &previous, task)
+    }
+
+    pub fn complete_compressed_task<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ModifyTaskTree<'info>>,
+        root: [u8; 32],
+        previous: CompressedTask,
+    ) -> Result<()> {
+        require!(
+            previous.status != TaskStatus::Done,
+            ErrorTask::AlreadyCompleted
+        );
+        let now = Clock::get()?.unix_timestamp;
+        let mut task = previous.clone();
+        task.status = TaskStatus::Done;
+        task.updated_at = now;
+        task.completed_at = Some(now);
+        ctx.accounts.profile.record_compressed_completed();
+        replace_compressed_task(&ctx, root, &previous, task)
+    }
+
+    // An older account is read through `TaskV0`, rebuilt as the current `Task` and written
+    // back over the whole body. Anyone can pay for the upgrade. The parent and `blocked_by`
+    // tasks come in as remaining accounts and have to be migrated first, old links were
+    // never counted, so the task is added to their `dependent_count` here
+    pub fn migrate_task<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MigrateTask<'info>>,
+    ) -> Result<()> {
+        let task_info = ctx.accounts.task.to_account_info();
+        require_keys_eq!(*task_info.owner, crate::ID, ErrorTask::InvalidTaskAccount);
+        let old_len = task_info.data_len();
+        require!(
+            old_len >= 8 && task_info.try_borrow_data()?[..8] == Task::DISCRIMINATOR,
+            ErrorTask::InvalidTaskAccount
+        );
+
+        // An old account can carry leftover bytes right where `version` goes, so it only
+        // counts as migrated when the whole current layout reads back with a known version
+        let old = {
+            let data = task_info.try_borrow_data()?;
+            if let Ok(current) = Task::try_deserialize(&mut &data[..]) {
+                require!(
+                    !(1..=Task::VERSION).contains(&current.version),
+                    ErrorTask::AlreadyMigrated
+                );
+            }
+            TaskV0::deserialize(&mut &data[8..]).map_err(|_| ErrorTask::InvalidTaskAccount)?
+        };
+        let task = Task::from(old);
+        count_against_dependencies(&task, ctx.remaining_accounts, true)?;
+
+        let new_len = old_len.max(Task::space(&task.title, &task.description));
+        task_info.realloc(new_len, true)?;
+        task.try_serialize(&mut &mut task_info.try_borrow_mut_data()?[..])?;
+
+        let rent = Rent::get()?.minimum_balance(new_len);
+        let lamports = task_info.lamports();
+        if rent > lamports {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: task_info,
+                    },
+                ),
+                rent - lamports,
+            )?;
+        }
+        msg!(
+            "Task migrated, Id: {}, Author: {}, Version: 0 -> {}",
+            task.task_id,
+            task.author,
+            task.version
+        );
+        Ok(())
+    }
+
+    // A board admin sets the rules, anyone can sponsor the pool afterwards
+    pub fn create_reward_pool(
+        ctx: Context<CreateRewardPool>,
+        reward_per_task: u64,
+        daily_cap: u32,
+    ) -> Result<()> {
+        require!(reward_per_task > 0, ErrorTask::InvalidAmount);
+        require!(daily_cap > 0, ErrorTask::InvalidAmount);
+
+        let reward_pool = &mut ctx.accounts.reward_pool;
+        reward_pool.board = ctx.accounts.board.key();
+        reward_pool.reward_per_task = reward_per_task;
+        reward_pool.daily_cap = daily_cap;
+        reward_pool.total_deposited = 0;
+        reward_pool.total_paid = 0;
+        msg!(
+            "Reward pool created, Board: {}, Per task: {}, Daily cap: {}",
+            ctx.accounts.board.name,
+            reward_per_task,
+            daily_cap
+        );
+        Ok(())
+    }
+
+    pub fn set_reward_rules(
+        ctx: Context<SetRewardRules>,
+        reward_per_task: u64,
+        daily_cap: u32,
+    ) -> Result<()> {
+        require!(reward_per_task > 0, ErrorTask::InvalidAmount);
+        require!(daily_cap > 0, ErrorTask::InvalidAmount);
+
+        let reward_pool = &mut ctx.accounts.reward_pool;
+        reward_pool.reward_per_task = reward_per_task;
+        reward_pool.daily_cap = daily_cap;
+        msg!(
+            "Reward rules updated, Board: {}, Per task: {}, Daily cap: {}",
+            ctx.accounts.board.name,
+            reward_per_task,
+            daily_cap
+        );
+        Ok(())
+    }
+
+    pub fn fund_reward_pool(ctx: Context<FundRewardPool>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorTask::InvalidAmount);
+
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.sponsor.to_account_info(),
+                    to: ctx.accounts.reward_pool.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let reward_pool = &mut ctx.accounts.reward_pool;
+        reward_pool.total_deposited = reward_pool.total_deposited.saturating_add(amount);
+        msg!(
+            "Reward pool funded, Board: {}, Sponsor: {}, Amount: {}",
+            reward_pool.board,
+            ctx.accounts.sponsor.key(),
+            amount
+        );
+        Ok(())
+    }
+
+    // Workers opt in once per pool, rewards collect on the tracker until withdrawn
+    pub fn join_reward_pool(ctx: Context<JoinRewardPool>) -> Result<()> {
+        let tracker = &mut ctx.accounts.reward_tracker;
+        tracker.pool = ctx.accounts.reward_pool.key();
+        tracker.user = ctx.accounts.user.key();
+        tracker.day = 0;
+        tracker.rewards_today = 0;
+        tracker.total_earned = 0;
+        msg!(
+            "Joined reward pool, Pool: {}, User: {}",
+            tracker.pool,
+            tracker.user
+        );
+        Ok(())
+    }
+
+    pub fn withdraw_rewards(ctx: Context<WithdrawRewards>) -> Result<()> {
+        let tracker_info = ctx.accounts.reward_tracker.to_account_info();
+        let amount = tracker_info
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(tracker_info.data_len()));
+        require!(amount > 0, ErrorTask::InvalidAmount);
+
+        **tracker_info.try_borrow_mut_lamports()? -= amount;
+        **ctx
+            .accounts
+            .user
+            .to_account_info()
+            .try_borrow_mut_lamports()? += amount;
+        msg!(
+            "Rewards withdrawn, User: {}, Amount: {}",
+            ctx.accounts.user.key(),
+            amount
+        );
+        Ok(())
+    }
+
+    // The funder takes the bounty back once the deadline set at funding has passed without
+    // the task being completed or waiting on review
+    pub fn refund_bounty(ctx: Context<RefundBounty>) -> Result<()> {
+        let deadline = ctx
+            .accounts
+            .escrow
+            .deadline
+            .ok_or(ErrorTask::DueDateRequired)?;
+        let task = &mut ctx.accounts.task;
+
+        require!(
+            Clock::get()?.unix_timestamp > deadline,
+            ErrorTask::DeadlineNotPassed
+        );
+        require!(!task.is_completed(), ErrorTask::TaskAlreadyCompleted);
+        require!(
+            task.status != TaskStatus::PendingReview,
+            ErrorTask::AwaitingReview
+        );
+
+        task.has_bounty = false;
+        msg!(
+            "Bounty refunded. Title: {}, Funder: {}, Amount: {}",
+            task.title,
+            ctx.accounts.funder.key(),
+            ctx.accounts.escrow.amount
+        );
+        Ok(())
+    }
+
+    // The token version of `refund_bounty`, the whole vault goes back to the funder
+    pub fn refund_bounty_spl(ctx: Context<RefundBountySpl>) -> Result<()> {
+        let deadline = ctx
+            .accounts
+            .token_escrow
+            .deadline
+            .ok_or(ErrorTask::DueDateRequired)?;
+        let task = &ctx.accounts.task;
+
+        require!(
+            Clock::get()?.unix_timestamp > deadline,
+            ErrorTask::DeadlineNotPassed
+        );
+        require!(!task.is_completed(), ErrorTask::TaskAlreadyCompleted);
+        require!(
+            task.status != TaskStatus::PendingReview,
+            ErrorTask::AwaitingReview
+        );
+
+        let amount = ctx.accounts.vault.amount;
+        let author = task.author;
+        let task_id = task.task_id.to_le_bytes();
+        let bump = [task.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"task", author.as_ref(), task_id.as_ref(), &bump]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.funder_token_account.to_account_info(),
+                    authority: ctx.accounts.task.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+        token_interface::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::CloseAccount {
+                account: ctx.accounts.vault.to_account_info(),
+                destination: ctx.accounts.funder.to_account_info(),
+                authority: ctx.accounts.task.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+
+        let task = &mut ctx.accounts.task;
+        task.has_token_bounty = false;
+        msg!(
+            "Bounty refunded. Title: {}, Funder: {}, Mint: {}, Amount: {}",
+            task.title,
+            ctx.accounts.funder.key(),
+            ctx.accounts.mint.key(),
+            amount
+        );
+        Ok(())
+    }
+
+    pub fn set_arbiter(ctx: Context<SetArbiter>, arbiter: Option<Pubkey>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.arbiter = arbiter;
+        msg!("Arbiter updated, Arbiter: {:?}", config.arbiter);
+        Ok(())
+    }
+
+    // Either side of a bounty, the funder or the accepted assignee, can freeze it. Only SOL
+    // bounties can be disputed, a token bounty is always paid out by `claim_bounty_spl`
+    pub fn raise_dispute(ctx: Context<RaiseDispute>, reason: String) -> Result<()> {
+        require!(reason.len() <= 200, ErrorTask::DisputeReasonTooLong);
+        require!(ctx.accounts.config.arbiter.is_some(), ErrorTask::NoArbiter);
+        let task = &ctx.accounts.task;
+        let party = ctx.accounts.party.key();
+        let worker = match task.assignee {
+            Some(assignee) if task.assignment_accepted => assignee,
+            _ => return err!(ErrorTask::NoWorker),
+        };
+        require!(
+            party == ctx.accounts.escrow.funder || party == worker,
+            ErrorTask::Unauthorized
+        );
+
+        let escrow = &mut ctx.accounts.escrow;
+        escrow.disputed = true;
+        escrow.worker = Some(worker);
+        emit_cpi!(DisputeRaised {
+            task: task.key(),
+            task_id: task.task_id,
+            raised_by: party,
+            reason,
+        });
+        Ok(())
+    }
+
+    // `worker_bps` of the escrowed lamports go to the assignee, the rest and the rent back
+    // to the funder
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, worker_bps: u16) -> Result<()> {
+        require!(worker_bps <= 10_000, ErrorTask::InvalidSplit);
+        let amount = ctx.accounts.escrow.amount;
+        let worker_amount = (amount as u128 * worker_bps as u128 / 10_000) as u64;
+
+        **ctx
+            .accounts
+            .escrow
+            .to_account_info()
+            .try_borrow_mut_lamports()? -= worker_amount;
+        **ctx
+            .accounts
+            .worker
+            .to_account_info()
+            .try_borrow_mut_lamports()? += worker_amount;
+
+        let task = &mut ctx.accounts.task;
+        task.has_bounty = false;
+        emit_cpi!(DisputeResolved {
+            task: task.key(),
+            task_id: task.task_id,
+            arbiter: ctx.accounts.arbiter.key(),
+            worker_amount,
+            funder_amount: amount - worker_amount,
+        });
+        Ok(())
+    }
+
+}
+
+#[derive(Accounts)]
+pub struct InitProfile<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        init,
+        payer = author,
+        space = 8 + UserProfile::INIT_SPACE,
+        seeds = [b"profile", author.key().as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    #[account(
+        init,
+        payer = author,
+        space = 8 + TaskIndex::INIT_SPACE,
+        seeds = [b"task_index", author.key().as_ref()],
+        bump,
+    )]
+    pub task_index: Account<'info, TaskIndex>,
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct InitGlobalStats<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + GlobalStats::INIT_SPACE,
+        seeds = [b"global_stats"],
+        bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config"],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, crate::program::TaskManager>,
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ ErrorTask::Unauthorized,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        has_one = admin @ ErrorTask::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(title: String, description: String)]
+pub struct CreateTask<'info> {
+    // Pays rent and the creation fee, a relayer in gasless flows or just the author
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub author: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"profile", author.key().as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    // Lifts the free open-task cap while it's active
+    #[account(seeds = [b"subscription", profile.author.as_ref()], bump)]
+    pub subscription: Option<Account<'info, Subscription>>,
+    #[account(
+        init,
+        payer = payer,
+        space = Task::space(&title, &description),
+        seeds = [b"task", author.key().as_ref(), profile.task_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub task: Account<'info, Task>,
+    // Missing on profiles from before `init_task_index`
+    #[account(
+        mut,
+        seeds = [b"task_index", author.key().as_ref()],
+        bump,
+    )]
+    pub task_index: Option<Account<'info, TaskIndex>>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+    #[account(
+        mut,
+        constraint = board.can_edit(&author.key()) @ ErrorTask::NotBoardEditor,
+    )]
+    pub board: Option<Account<'info, Board>>,
+    // Required whenever the task sits on a board, checked in `log_activity`
+    #[account(mut)]
+    pub activity_log: Option<Account<'info, ActivityLog>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(task_id: u64, title: String, description: String)]
+pub struct CreateTaskIfNeeded<'info> {
+    // Pays rent and the creation fee, a relayer in gasless flows or just the author
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub author: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"profile", author.key().as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    // Lifts the free open-task cap while it's active
+    #[account(seeds = [b"subscription", profile.author.as_ref()], bump)]
+    pub subscription: Option<Account<'info, Subscription>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = Task::space(&title, &description),
+        seeds = [b"task", author.key().as_ref(), task_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub task: Account<'info, Task>,
+    // Missing on profiles from before `init_task_index`
+    #[account(
+        mut,
+        seeds = [b"task_index", author.key().as_ref()],
+        bump,
+    )]
+    pub task_index: Option<Account<'info, TaskIndex>>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+    #[account(
+        mut,
+        constraint = board.can_edit(&author.key()) @ ErrorTask::NotBoardEditor,
+    )]
+    pub board: Option<Account<'info, Board>>,
+    // Required whenever the task sits on a board, checked in `log_activity`
+    #[account(mut)]
+    pub activity_log: Option<Account<'info, ActivityLog>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(title: String, description: String)]
+pub struct CreateSubtask<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"profile", author.key().as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    // Lifts the free open-task cap while it's active
+    #[account(seeds = [b"subscription", profile.author.as_ref()], bump)]
+    pub subscription: Option<Account<'info, Subscription>>,
+    #[account(
+        mut,
+        seeds = [b"task", author.key().as_ref(), parent.task_id.to_le_bytes().as_ref()],
+        bump = parent.bump,
+        constraint = parent.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub parent: Account<'info, Task>,
+    #[account(
+        init,
+        payer = author,
+        space = Task::space(&title, &description),
+        seeds = [b"task", author.key().as_ref(), profile.task_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub task: Account<'info, Task>,
+    // Missing on profiles from before `init_task_index`
+    #[account(
+        mut,
+        seeds = [b"task_index", author.key().as_ref()],
+        bump,
+    )]
+    pub task_index: Option<Account<'info, TaskIndex>>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(description: String)]
+pub struct UpdateTask<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() || delegate.is_some() @ ErrorTask::Unauthorized,
+        realloc = Task::space(&task.title, &description),
+        realloc::payer = author,
+        realloc::zero = false,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(seeds = [b"delegate", task.authority.as_ref(), author.key().as_ref()], bump)]
+    pub delegate: Option<Account<'info, Delegate>>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    // Required whenever the task sits on a board, checked in `log_activity`
+    #[account(mut)]
+    pub activity_log: Option<Account<'info, ActivityLog>>,
+    // Passed when the edit should leave an audit trail
+    #[account(
+        init,
+        payer = author,
+        space = 8 + TaskRevision::INIT_SPACE,
+        seeds = [b"revision", task.key().as_ref(), task.revision_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub revision: Option<Account<'info, TaskRevision>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(title: String)]
+pub struct RenameTask<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        realloc = Task::space(&title, &task.description),
+        realloc::payer = author,
+        realloc::zero = false,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CompleteTask<'info> {
+    // The authority, the accepted assignee or one of the authority's session keys
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.can_complete(&signer.key()) || delegate.is_some() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(seeds = [b"delegate", task.authority.as_ref(), signer.key().as_ref()], bump)]
+    pub delegate: Option<Account<'info, Delegate>>,
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+    #[account(
+        mut,
+        seeds = [b"profile", task.author.as_ref()],
+        bump,
+        constraint = profile.author == task.author @ ErrorTask::Unauthorized,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    // Required whenever the task sits on a board, checked in `log_activity`
+    #[account(mut)]
+    pub activity_log: Option<Account<'info, ActivityLog>>,
+    #[account(
+        mut,
+        constraint = task.milestone == Some(milestone.key()) @ ErrorTask::MilestoneMismatch,
+    )]
+    pub milestone: Option<Account<'info, Milestone>>,
+    #[account(
+        mut,
+        seeds = [b"stake", task.key().as_ref()],
+        bump,
+    )]
+    pub stake: Option<Account<'info, Stake>>,
+    /// CHECK: only receives the refunded stake, checked against `task.author`
+    #[account(mut, address = task.author @ ErrorTask::Unauthorized)]
+    pub author: Option<UncheckedAccount<'info>>,
+    pub memo_program: Option<Program<'info, Memo>>,
+    #[account(
+        mut,
+        seeds = [b"reward_pool", reward_pool.board.as_ref()],
+        bump,
+    )]
+    pub reward_pool: Option<Account<'info, RewardPool>>,
+    // The worker's tracker for `reward_pool`, checked in `pay_reward`
+    #[account(mut)]
+    pub reward_tracker: Option<Account<'info, RewardTracker>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetStatus<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        mut,
+        seeds = [b"profile", task.author.as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    // Required whenever the task sits on a board, checked in `log_activity`
+    #[account(mut)]
+    pub activity_log: Option<Account<'info, ActivityLog>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ReopenTask<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        mut,
+        seeds = [b"profile", task.author.as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    // Required whenever the task sits on a board, checked in `log_activity`
+    #[account(mut)]
+    pub activity_log: Option<Account<'info, ActivityLog>>,
+    #[account(
+        mut,
+        constraint = task.milestone == Some(milestone.key()) @ ErrorTask::MilestoneMismatch,
+    )]
+    pub milestone: Option<Account<'info, Milestone>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct SetPriority<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct AddTag<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveTag<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct AddDependency<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(mut)]
+    pub dependency: Account<'info, Task>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveDependency<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(mut)]
+    pub dependency_task: Option<Account<'info, Task>>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AssignTask<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAssignment<'info> {
+    pub assignee: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.assignee == Some(assignee.key()) @ ErrorTask::NotAssignee,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DeleteTask<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        close = author,
+    )]
+    pub task: Account<'info, Task>,
+    // Missing on profiles from before `init_task_index`
+    #[account(
+        mut,
+        seeds = [b"task_index", task.author.as_ref()],
+        bump,
+    )]
+    pub task_index: Option<Account<'info, TaskIndex>>,
+    #[account(
+        mut,
+        seeds = [b"profile", task.author.as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    #[account(mut)]
+    pub board: Option<Account<'info, Board>>,
+    // Required whenever the task sits on a board, checked in `log_activity`
+    #[account(mut)]
+    pub activity_log: Option<Account<'info, ActivityLog>>,
+    #[account(
+        mut,
+        seeds = [b"escrow", task.key().as_ref()],
+        bump,
+    )]
+    pub escrow: Option<Account<'info, Escrow>>,
+    /// CHECK: only receives the refunded bounty, checked against `escrow.funder`
+    #[account(mut)]
+    pub funder: Option<UncheckedAccount<'info>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct FundTask<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        init,
+        payer = funder,
+        space = 8 + Escrow::INIT_SPACE,
+        seeds = [b"escrow", task.key().as_ref()],
+        bump,
+    )]
+    pub escrow: Account<'info, Escrow>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimBounty<'info> {
+    #[account(mut)]
+    pub assignee: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.is_completed() @ ErrorTask::TaskNotCompleted,
+        constraint = task.assignment_accepted
+            && task.assignee == Some(assignee.key()) @ ErrorTask::NotAssignee,
+        constraint = task.authority != assignee.key() @ ErrorTask::SelfClaim,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        mut,
+        seeds = [b"escrow", task.key().as_ref()],
+        bump,
+        constraint = escrow.funder != assignee.key() @ ErrorTask::SelfClaim,
+        constraint = !escrow.disputed @ ErrorTask::BountyDisputed,
+        has_one = funder @ ErrorTask::EscrowMismatch,
+        close = funder,
+    )]
+    pub escrow: Account<'info, Escrow>,
+    /// CHECK: receives the escrow rent and any unneeded USD cap back, checked by `has_one`
+    /// on the escrow
+    #[account(mut)]
+    pub funder: UncheckedAccount<'info>,
+    // Pyth SOL/USD update, only needed for USD bounties
+    pub price_update: Option<Account<'info, PriceUpdateV2>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct FundTaskSpl<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = funder,
+        token::token_program = token_program,
+    )]
+    pub funder_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(
+        init,
+        payer = funder,
+        space = 8 + TokenEscrow::INIT_SPACE,
+        seeds = [b"token_escrow", task.key().as_ref()],
+        bump,
+    )]
+    pub token_escrow: Account<'info, TokenEscrow>,
+    // Anyone can create a task's associated token account up front, so it's reused if it's
+    // already there
+    #[account(
+        init_if_needed,
+        payer = funder,
+        associated_token::mint = mint,
+        associated_token::authority = task,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimBountySpl<'info> {
+    #[account(mut)]
+    pub assignee: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.is_completed() @ ErrorTask::TaskNotCompleted,
+        constraint = task.assignment_accepted
+            && task.assignee == Some(assignee.key()) @ ErrorTask::NotAssignee,
+        constraint = task.authority != assignee.key() @ ErrorTask::SelfClaim,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        mut,
+        seeds = [b"token_escrow", task.key().as_ref()],
+        bump,
+        constraint = token_escrow.funder != assignee.key() @ ErrorTask::SelfClaim,
+        has_one = funder @ ErrorTask::EscrowMismatch,
+        has_one = mint @ ErrorTask::EscrowMismatch,
+        close = funder,
+    )]
+    pub token_escrow: Account<'info, TokenEscrow>,
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = task,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = assignee,
+        token::token_program = token_program,
+    )]
+    pub assignee_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    /// CHECK: only receives the escrow and vault rent back, checked by `has_one` on the escrow
+    #[account(mut)]
+    pub funder: UncheckedAccount<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PostComment<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        init,
+        payer = author,
+        space = 8 + Comment::INIT_SPACE,
+        seeds = [b"comment", task.key().as_ref(), task.comment_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub comment: Account<'info, Comment>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct EditComment<'info> {
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"comment", comment.task.as_ref(), comment.comment_id.to_le_bytes().as_ref()],
+        bump,
+        has_one = author @ ErrorTask::Unauthorized,
+    )]
+    pub comment: Account<'info, Comment>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct DeleteComment<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"comment", comment.task.as_ref(), comment.comment_id.to_le_bytes().as_ref()],
+        bump,
+        has_one = author @ ErrorTask::Unauthorized,
+        close = author,
+    )]
+    pub comment: Account<'info, Comment>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct CreateBoard<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"profile", owner.key().as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Board::INIT_SPACE,
+        seeds = [b"board", owner.key().as_ref(), profile.board_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub board: Account<'info, Board>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + ActivityLog::INIT_SPACE,
+        seeds = [b"activity", board.key().as_ref()],
+        bump,
+    )]
+    pub activity_log: Account<'info, ActivityLog>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(member: Pubkey)]
+pub struct InviteMember<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"board", board.owner.as_ref(), board.board_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = board.role_of(&admin.key()) == Some(Role::Admin) @ ErrorTask::Unauthorized,
+    )]
+    pub board: Account<'info, Board>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Invite::INIT_SPACE,
+        seeds = [b"invite", board.key().as_ref(), member.as_ref()],
+        bump,
+    )]
+    pub invite: Account<'info, Invite>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptInvite<'info> {
+    pub member: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"board", board.owner.as_ref(), board.board_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub board: Account<'info, Board>,
+    #[account(
+        mut,
+        seeds = [b"invite", board.key().as_ref(), member.key().as_ref()],
+        bump,
+        has_one = board,
+        has_one = member,
+        has_one = inviter,
+        close = inviter,
+    )]
+    pub invite: Account<'info, Invite>,
+    /// CHECK: only receives the invite rent back, checked by `has_one` on the invite
+    #[account(mut)]
+    pub inviter: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"activity", board.key().as_ref()],
+        bump,
+    )]
+    pub activity_log: Account<'info, ActivityLog>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct DeclineInvite<'info> {
+    pub member: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"invite", invite.board.as_ref(), member.key().as_ref()],
+        bump,
+        has_one = member,
+        has_one = inviter,
+        close = inviter,
+    )]
+    pub invite: Account<'info, Invite>,
+    /// CHECK: only receives the invite rent back, checked by `has_one` on the invite
+    #[account(mut)]
+    pub inviter: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveMember<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"board", board.owner.as_ref(), board.board_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = board.role_of(&admin.key()) == Some(Role::Admin) @ ErrorTask::Unauthorized,
+    )]
+    pub board: Account<'info, Board>,
+    #[account(
+        mut,
+        seeds = [b"activity", board.key().as_ref()],
+        bump,
+    )]
+    pub activity_log: Account<'info, ActivityLog>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct ChangeRole<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"board", board.owner.as_ref(), board.board_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = board.role_of(&admin.key()) == Some(Role::Admin) @ ErrorTask::Unauthorized,
+    )]
+    pub board: Account<'info, Board>,
+    #[account(
+        mut,
+        seeds = [b"activity", board.key().as_ref()],
+        bump,
+    )]
+    pub activity_log: Account<'info, ActivityLog>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseBoard<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"board", owner.key().as_ref(), board.board_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = board.authority.is_none()
+            || board.authority == authority.as_ref().map(|a| a.key()) @ ErrorTask::Unauthorized,
+        close = owner,
+    )]
+    pub board: Account<'info, Board>,
+    // Boards created before the log existed may not have one
+    #[account(
+        mut,
+        seeds = [b"activity", board.key().as_ref()],
+        bump,
+        close = owner,
+    )]
+    pub activity_log: Option<Account<'info, ActivityLog>>,
+    // Signs next to the owner once the board has a governance authority
+    pub authority: Option<Signer<'info>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct StakeTask<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        init,
+        payer = author,
+        space = 8 + Stake::INIT_SPACE,
+        seeds = [b"stake", task.key().as_ref()],
+        bump,
+    )]
+    pub stake: Account<'info, Stake>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct SlashExpired<'info> {
+    pub cranker: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        mut,
+        seeds = [b"stake", task.key().as_ref()],
+        bump,
+        has_one = author @ ErrorTask::StakeMismatch,
+        close = author,
+    )]
+    pub stake: Account<'info, Stake>,
+    /// CHECK: only receives the stake rent back, checked by `has_one` on the stake
+    #[account(mut)]
+    pub author: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+    /// CHECK: Switchboard pull feed, checked against `stake.oracle` and parsed in the handler
+    pub oracle: Option<UncheckedAccount<'info>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct CreateTaskLarge<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"profile", author.key().as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    // Lifts the free open-task cap while it's active
+    #[account(seeds = [b"subscription", profile.author.as_ref()], bump)]
+    pub subscription: Option<Account<'info, Subscription>>,
+    #[account(
+        init,
+        payer = author,
+        space = 8 + std::mem::size_of::<TaskLarge>(),
+        seeds = [b"task_large", author.key().as_ref(), profile.task_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub task: AccountLoader<'info, TaskLarge>,
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTaskLarge<'info> {
+    pub author: Signer<'info>,
+    #[account(mut, has_one = author @ ErrorTask::Unauthorized)]
+    pub task: AccountLoader<'info, TaskLarge>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct DeleteTaskLarge<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        has_one = author @ ErrorTask::Unauthorized,
+        close = author,
+    )]
+    pub task: AccountLoader<'info, TaskLarge>,
+    #[account(
+        mut,
+        seeds = [b"profile", author.key().as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct ArchiveTask<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct UnarchiveTask<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CompleteMany<'info> {
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"profile", author.key().as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(title: Option<String>)]
+pub struct CloneTask<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"profile", author.key().as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    // Lifts the free open-task cap while it's active
+    #[account(seeds = [b"subscription", profile.author.as_ref()], bump)]
+    pub subscription: Option<Account<'info, Subscription>>,
+    #[account(
+        seeds = [b"task", author.key().as_ref(), source.task_id.to_le_bytes().as_ref()],
+        bump = source.bump,
+    )]
+    pub source: Account<'info, Task>,
+    #[account(
+        init,
+        payer = author,
+        space = Task::space(title.as_deref().unwrap_or(&source.title), &source.description),
+        seeds = [b"task", author.key().as_ref(), profile.task_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub task: Account<'info, Task>,
+    // Missing on profiles from before `init_task_index`
+    #[account(
+        mut,
+        seeds = [b"task_index", author.key().as_ref()],
+        bump,
+    )]
+    pub task_index: Option<Account<'info, TaskIndex>>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeTransfer<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptTransfer<'info> {
+    #[account(mut)]
+    pub new_author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"profile", new_author.key().as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    // Lifts the free open-task cap while it's active
+    #[account(seeds = [b"subscription", profile.author.as_ref()], bump)]
+    pub subscription: Option<Account<'info, Subscription>>,
+    #[account(
+        mut,
+        seeds = [b"task", old_task.author.as_ref(), old_task.task_id.to_le_bytes().as_ref()],
+        bump = old_task.bump,
+        constraint = old_task.pending_author == Some(new_author.key()) @ ErrorTask::NoPendingTransfer,
+        close = author,
+        constraint = old_task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub old_task: Account<'info, Task>,
+    #[account(
+        mut,
+        seeds = [b"profile", old_task.author.as_ref()],
+        bump,
+    )]
+    pub old_profile: Account<'info, UserProfile>,
+    // Missing on profiles from before `init_task_index`
+    #[account(
+        mut,
+        seeds = [b"task_index", old_task.author.as_ref()],
+        bump,
+    )]
+    pub old_task_index: Option<Account<'info, TaskIndex>>,
+    /// CHECK: the previous author, only receives the old task's rent
+    #[account(mut, address = old_task.author @ ErrorTask::Unauthorized)]
+    pub author: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = new_author,
+        space = Task::space(&old_task.title, &old_task.description),
+        seeds = [b"task", new_author.key().as_ref(), profile.task_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub task: Account<'info, Task>,
+    // Missing on profiles from before `init_task_index`
+    #[account(
+        mut,
+        seeds = [b"task_index", new_author.key().as_ref()],
+        bump,
+    )]
+    pub task_index: Option<Account<'info, TaskIndex>>,
+    pub system_program: Program<'info, System>,
+    #[account(constraint = old_task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        has_one = admin @ ErrorTask::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        has_one = admin @ ErrorTask::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(title: String, description: String)]
+pub struct CreateRecurring<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"profile", author.key().as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    #[account(
+        init,
+        payer = author,
+        space = RecurringTask::space(&title, &description),
+        seeds = [b"recurring", author.key().as_ref(), profile.recurring_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub recurring: Account<'info, RecurringTask>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SpawnRecurrence<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"recurring", recurring.author.as_ref(), recurring.recurring_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub recurring: Account<'info, RecurringTask>,
+    #[account(
+        mut,
+        seeds = [b"profile", recurring.author.as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    // Lifts the free open-task cap while it's active
+    #[account(seeds = [b"subscription", profile.author.as_ref()], bump)]
+    pub subscription: Option<Account<'info, Subscription>>,
+    #[account(
+        init,
+        payer = cranker,
+        space = Task::space(&recurring.title, &recurring.description),
+        seeds = [b"task", recurring.author.as_ref(), profile.task_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub task: Account<'info, Task>,
+    // Missing on profiles from before `init_task_index`
+    #[account(
+        mut,
+        seeds = [b"task_index", recurring.author.as_ref()],
+        bump,
+    )]
+    pub task_index: Option<Account<'info, TaskIndex>>,
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct CancelRecurring<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"recurring", author.key().as_ref(), recurring.recurring_id.to_le_bytes().as_ref()],
+        bump,
+        close = author,
+    )]
+    pub recurring: Account<'info, RecurringTask>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct InitReminder<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        init,
+        payer = author,
+        space = 8 + Reminder::INIT_SPACE,
+        seeds = [b"reminder", author.key().as_ref()],
+        bump,
+    )]
+    pub reminder: Account<'info, Reminder>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateReminder<'info> {
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"reminder", author.key().as_ref()],
+        bump,
+    )]
+    pub reminder: Account<'info, Reminder>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Tick<'info> {
+    // The author or their registered thread
+    pub caller: Signer<'info>,
+    #[account(
+        seeds = [b"reminder", reminder.author.as_ref()],
+        bump,
+        constraint = caller.key() == reminder.author
+            || reminder.thread == Some(caller.key()) @ ErrorTask::Unauthorized,
+    )]
+    pub reminder: Account<'info, Reminder>,
+}
+
+#[derive(Accounts)]
+pub struct SetExpiryParams<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        has_one = admin @ ErrorTask::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExpireTask<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        mut,
+        seeds = [b"profile", task.author.as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    #[account(
+        mut,
+        seeds = [b"escrow", task.key().as_ref()],
+        bump,
+    )]
+    pub escrow: Option<Account<'info, Escrow>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct MintCompletionBadge<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        seeds = [b"profile", task.author.as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    /// CHECK: PDA that only signs as the badge mint, freeze and update authority
+    #[account(seeds = [b"badge_authority"], bump)]
+    pub badge_authority: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = author,
+        mint::decimals = 0,
+        mint::authority = badge_authority,
+        mint::freeze_authority = badge_authority,
+        seeds = [b"badge", task.key().as_ref()],
+        bump,
+    )]
+    pub mint: Account<'info, Mint>,
+    #[account(
+        init,
+        payer = author,
+        associated_token::mint = mint,
+        associated_token::authority = author,
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+    /// CHECK: created and validated by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata", metadata_program.key().as_ref(), mint.key().as_ref()],
+        bump,
+        seeds::program = metadata_program.key(),
+    )]
+    pub metadata: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct SetBadgeTree<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        has_one = admin @ ErrorTask::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct MintCnftBadge<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        seeds = [b"profile", task.author.as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    /// CHECK: PDA that only signs as the tree delegate
+    #[account(seeds = [b"badge_authority"], bump)]
+    pub badge_authority: UncheckedAccount<'info>,
+    /// CHECK: validated against the configured tree here and by bubblegum
+    #[account(
+        mut,
+        constraint = config.badge_tree == Some(merkle_tree.key()) @ ErrorTask::BadgeTreeMismatch,
+    )]
+    pub merkle_tree: UncheckedAccount<'info>,
+    /// CHECK: bubblegum's tree config PDA, validated by bubblegum
+    #[account(
+        mut,
+        seeds = [merkle_tree.key().as_ref()],
+        bump,
+        seeds::program = bubblegum_program.key(),
+    )]
+    pub tree_config: UncheckedAccount<'info>,
+    /// CHECK: checked against the bubblegum program id
+    #[account(address = mpl_bubblegum::ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+    pub log_wrapper: Program<'info, Noop>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct InitLeaderboard<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Leaderboard::INIT_SPACE,
+        seeds = [b"leaderboard"],
+        bump,
+    )]
+    pub leaderboard: Account<'info, Leaderboard>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateLeaderboard<'info> {
+    #[account(
+        seeds = [b"profile", profile.author.as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    #[account(
+        mut,
+        seeds = [b"leaderboard"],
+        bump,
+    )]
+    pub leaderboard: Account<'info, Leaderboard>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+#[instruction(achievement_id: u64)]
+pub struct CreateAchievement<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Achievement::INIT_SPACE,
+        seeds = [b"achievement", achievement_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub achievement: Account<'info, Achievement>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        has_one = admin @ ErrorTask::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UnlockAchievement<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"profile", author.key().as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    #[account(
+        seeds = [b"achievement", achievement.achievement_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub achievement: Account<'info, Achievement>,
+    #[account(
+        init,
+        payer = author,
+        space = 8 + AchievementUnlock::INIT_SPACE,
+        seeds = [b"unlock", achievement.key().as_ref(), author.key().as_ref()],
+        bump,
+    )]
+    pub unlock: Account<'info, AchievementUnlock>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+#[instruction(assignee: Pubkey)]
+pub struct InitReputation<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Reputation::INIT_SPACE,
+        seeds = [b"reputation", assignee.as_ref()],
+        bump,
+    )]
+    pub reputation: Account<'info, Reputation>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptWork<'info> {
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        mut,
+        seeds = [b"reputation", reputation.assignee.as_ref()],
+        bump,
+        constraint = task.assignee == Some(reputation.assignee) @ ErrorTask::Unauthorized,
+        constraint = reputation.assignee != task.authority @ ErrorTask::SelfAccept,
+    )]
+    pub reputation: Account<'info, Reputation>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct AddItem<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct ToggleItem<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveItem<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct SetProgress<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct AddAttachment<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveAttachment<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(is_private: bool, description: String)]
+pub struct SetPrivate<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        realloc = Task::space(&task.title, &description),
+        realloc::payer = author,
+        realloc::zero = false,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(title: String, content_uri: String)]
+pub struct CreateTaskHashed<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"profile", author.key().as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    // Lifts the free open-task cap while it's active
+    #[account(seeds = [b"subscription", profile.author.as_ref()], bump)]
+    pub subscription: Option<Account<'info, Subscription>>,
+    #[account(
+        init,
+        payer = author,
+        space = Task::space(&title, &content_uri),
+        seeds = [b"task", author.key().as_ref(), profile.task_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub task: Account<'info, Task>,
+    // Missing on profiles from before `init_task_index`
+    #[account(
+        mut,
+        seeds = [b"task_index", author.key().as_ref()],
+        bump,
+    )]
+    pub task_index: Option<Account<'info, TaskIndex>>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+    #[account(
+        mut,
+        constraint = board.can_edit(&author.key()) @ ErrorTask::NotBoardEditor,
+    )]
+    pub board: Option<Account<'info, Board>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(content_uri: String)]
+pub struct SetContent<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        realloc = Task::space(&task.title, &content_uri),
+        realloc::payer = author,
+        realloc::zero = false,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct InitTaskIndex<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        init,
+        payer = author,
+        space = 8 + TaskIndex::INIT_SPACE,
+        seeds = [b"task_index", author.key().as_ref()],
+        bump,
+    )]
+    pub task_index: Account<'info, TaskIndex>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct CreateTemplate<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"profile", author.key().as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    #[account(
+        init,
+        payer = author,
+        space = 8 + Template::INIT_SPACE,
+        seeds = [b"template", author.key().as_ref(), profile.template_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub template: Account<'info, Template>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InstantiateTemplate<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"profile", author.key().as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    // Lifts the free open-task cap while it's active
+    #[account(seeds = [b"subscription", profile.author.as_ref()], bump)]
+    pub subscription: Option<Account<'info, Subscription>>,
+    #[account(
+        mut,
+        seeds = [b"template", author.key().as_ref(), template.template_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub template: Account<'info, Template>,
+    #[account(
+        init,
+        payer = author,
+        space = Task::space(
+            &template.title_for(template.use_count + 1),
+            &template.description,
+        ),
+        seeds = [b"task", author.key().as_ref(), profile.task_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub task: Account<'info, Task>,
+    // Missing on profiles from before `init_task_index`
+    #[account(
+        mut,
+        seeds = [b"task_index", author.key().as_ref()],
+        bump,
+    )]
+    pub task_index: Option<Account<'info, TaskIndex>>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReorderTask<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct PinTask<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        mut,
+        seeds = [b"profile", task.author.as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct UnpinTask<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        mut,
+        seeds = [b"profile", task.author.as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct SetMaxSnoozes<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        has_one = admin @ ErrorTask::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct SetAllowedMints<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        has_one = admin @ ErrorTask::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct SetSubscriptionTiers<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        has_one = admin @ ErrorTask::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct SetTimeOracle<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        has_one = admin @ ErrorTask::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct SnoozeTask<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct CreateMilestone<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"profile", author.key().as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    #[account(
+        init,
+        payer = author,
+        space = 8 + Milestone::INIT_SPACE,
+        seeds = [b"milestone", author.key().as_ref(), profile.milestone_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub milestone: Account<'info, Milestone>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct LinkMilestone<'info> {
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        mut,
+        seeds = [b"milestone", task.author.as_ref(), milestone.milestone_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub milestone: Account<'info, Milestone>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UnlinkMilestone<'info> {
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        mut,
+        constraint = task.milestone == Some(milestone.key()) @ ErrorTask::MilestoneMismatch,
+    )]
+    pub milestone: Account<'info, Milestone>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct CreateSprint<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"profile", author.key().as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    #[account(
+        init,
+        payer = author,
+        space = 8 + Sprint::INIT_SPACE,
+        seeds = [b"sprint", author.key().as_ref(), profile.sprint_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub sprint: Account<'info, Sprint>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct AddToSprint<'info> {
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        mut,
+        seeds = [b"sprint", task.author.as_ref(), sprint.sprint_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub sprint: Account<'info, Sprint>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct LeaveSprint<'info> {
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        mut,
+        constraint = task.sprint == Some(sprint.key()) @ ErrorTask::SprintMismatch,
+    )]
+    pub sprint: Account<'info, Sprint>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct CloseSprint<'info> {
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"sprint", author.key().as_ref(), sprint.sprint_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = !sprint.is_closed @ ErrorTask::SprintNotOpen,
+    )]
+    pub sprint: Account<'info, Sprint>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct CreateLabel<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        init,
+        payer = author,
+        space = 8 + Label::INIT_SPACE,
+        seeds = [b"label", author.key().as_ref(), name.as_bytes()],
+        bump,
+    )]
+    pub label: Account<'info, Label>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct DeleteLabel<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"label", author.key().as_ref(), label.name.as_bytes()],
+        bump,
+        close = author,
+    )]
+    pub label: Account<'info, Label>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct ApplyLabel<'info> {
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        mut,
+        seeds = [b"label", task.author.as_ref(), label.name.as_bytes()],
+        bump,
+    )]
+    pub label: Account<'info, Label>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveLabel<'info> {
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        mut,
+        seeds = [b"label", task.author.as_ref(), label.name.as_bytes()],
+        bump,
+    )]
+    pub label: Account<'info, Label>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateTasks<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"profile", author.key().as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    // Lifts the free open-task cap while it's active
+    #[account(seeds = [b"subscription", profile.author.as_ref()], bump)]
+    pub subscription: Option<Account<'info, Subscription>>,
+    // Missing on profiles from before `init_task_index`
+    #[account(
+        mut,
+        seeds = [b"task_index", author.key().as_ref()],
+        bump,
+    )]
+    pub task_index: Option<Account<'info, TaskIndex>>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SweepCompleted<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"profile", author.key().as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    // Missing on profiles from before `init_task_index`
+    #[account(
+        mut,
+        seeds = [b"task_index", author.key().as_ref()],
+        bump,
+    )]
+    pub task_index: Option<Account<'info, TaskIndex>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct TrashTask<'info> {
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        mut,
+        seeds = [b"profile", task.author.as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct RestoreTask<'info> {
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        mut,
+        seeds = [b"profile", task.author.as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    // Lifts the free open-task cap while it's active
+    #[account(seeds = [b"subscription", profile.author.as_ref()], bump)]
+    pub subscription: Option<Account<'info, Subscription>>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PurgeTask<'info> {
+    pub cranker: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        close = author,
+    )]
+    pub task: Account<'info, Task>,
+    /// CHECK: only receives the task's rent, checked against `task.author`
+    #[account(mut, address = task.author @ ErrorTask::Unauthorized)]
+    pub author: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"profile", task.author.as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    // Missing on profiles from before `init_task_index`
+    #[account(
+        mut,
+        seeds = [b"task_index", task.author.as_ref()],
+        bump,
+    )]
+    pub task_index: Option<Account<'info, TaskIndex>>,
+    #[account(
+        mut,
+        constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch,
+    )]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct InitActivityLog<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [b"board", owner.key().as_ref(), board.board_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = board.authority.is_none()
+            || board.authority == authority.as_ref().map(|a| a.key()) @ ErrorTask::Unauthorized,
+    )]
+    pub board: Account<'info, Board>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + ActivityLog::INIT_SPACE,
+        seeds = [b"activity", board.key().as_ref()],
+        bump,
+    )]
+    pub activity_log: Account<'info, ActivityLog>,
+    pub system_program: Program<'info, System>,
+    // Signs next to the owner once the board has a governance authority
+    pub authority: Option<Signer<'info>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct SetReviewer<'info> {
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ApproveCompletion<'info> {
+    pub reviewer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.reviewer == Some(reviewer.key()) @ ErrorTask::NotReviewer,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+    #[account(
+        mut,
+        seeds = [b"profile", task.author.as_ref()],
+        bump,
+        constraint = profile.author == task.author @ ErrorTask::Unauthorized,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    #[account(
+        mut,
+        constraint = task.milestone == Some(milestone.key()) @ ErrorTask::MilestoneMismatch,
+    )]
+    pub milestone: Option<Account<'info, Milestone>>,
+    #[account(
+        mut,
+        seeds = [b"stake", task.key().as_ref()],
+        bump,
+    )]
+    pub stake: Option<Account<'info, Stake>>,
+    /// CHECK: only receives the refunded stake, checked against `task.author`
+    #[account(mut, address = task.author @ ErrorTask::Unauthorized)]
+    pub author: Option<UncheckedAccount<'info>>,
+    #[account(
+        mut,
+        seeds = [b"reward_pool", reward_pool.board.as_ref()],
+        bump,
+    )]
+    pub reward_pool: Option<Account<'info, RewardPool>>,
+    // The worker's tracker for `reward_pool`, checked in `pay_reward`
+    #[account(mut)]
+    pub reward_tracker: Option<Account<'info, RewardTracker>>,
+    // Required whenever the task sits on a board, checked in `log_activity`
+    #[account(mut)]
+    pub activity_log: Option<Account<'info, ActivityLog>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RequestChanges<'info> {
+    pub reviewer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.reviewer == Some(reviewer.key()) @ ErrorTask::NotReviewer,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    // Required whenever the task sits on a board, checked in `log_activity`
+    #[account(mut)]
+    pub activity_log: Option<Account<'info, ActivityLog>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct SetRequiredApprovals<'info> {
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ApproveTask<'info> {
+    pub approver: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Account<'info, Board>,
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump,
+    )]
+    pub global_stats: Account<'info, GlobalStats>,
+    #[account(
+        mut,
+        seeds = [b"profile", task.author.as_ref()],
+        bump,
+        constraint = profile.author == task.author @ ErrorTask::Unauthorized,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    #[account(
+        mut,
+        constraint = task.milestone == Some(milestone.key()) @ ErrorTask::MilestoneMismatch,
+    )]
+    pub milestone: Option<Account<'info, Milestone>>,
+    #[account(
+        mut,
+        seeds = [b"stake", task.key().as_ref()],
+        bump,
+    )]
+    pub stake: Option<Account<'info, Stake>>,
+    /// CHECK: only receives the refunded stake, checked against `task.author`
+    #[account(mut, address = task.author @ ErrorTask::Unauthorized)]
+    pub author: Option<UncheckedAccount<'info>>,
+    #[account(
+        mut,
+        seeds = [b"reward_pool", reward_pool.board.as_ref()],
+        bump,
+    )]
+    pub reward_pool: Option<Account<'info, RewardPool>>,
+    // The worker's tracker for `reward_pool`, checked in `pay_reward`
+    #[account(mut)]
+    pub reward_tracker: Option<Account<'info, RewardTracker>>,
+    // Required whenever the task sits on a board, checked in `log_activity`
+    #[account(mut)]
+    pub activity_log: Option<Account<'info, ActivityLog>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetTaskAuthority<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        has_one = authority @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+#[instruction(session_key: Pubkey)]
+pub struct AddDelegate<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Delegate::INIT_SPACE,
+        seeds = [b"delegate", owner.key().as_ref(), session_key.as_ref()],
+        bump,
+    )]
+    pub delegate: Account<'info, Delegate>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeDelegate<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"delegate", owner.key().as_ref(), delegate.session_key.as_ref()],
+        bump,
+        close = owner,
+    )]
+    pub delegate: Account<'info, Delegate>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct TipTask<'info> {
+    #[account(mut)]
+    pub tipper: Signer<'info>,
+    #[account(
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    /// CHECK: only receives the lamport tip, checked against `task.author`
+    #[account(mut, address = task.author @ ErrorTask::Unauthorized)]
+    pub author: UncheckedAccount<'info>,
+    // Token tips pass all four, lamport tips none of them
+    pub mint: Option<InterfaceAccount<'info, token_interface::Mint>>,
+    #[account(mut)]
+    pub tipper_token_account: Option<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    #[account(mut)]
+    pub author_token_account: Option<InterfaceAccount<'info, token_interface::TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct PurchaseSubscription<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        init,
+        payer = author,
+        space = 8 + Subscription::INIT_SPACE,
+        seeds = [b"subscription", author.key().as_ref()],
+        bump,
+    )]
+    pub subscription: Account<'info, Subscription>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct RenewSubscription<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"subscription", author.key().as_ref()],
+        bump,
+    )]
+    pub subscription: Account<'info, Subscription>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct SetBoardAuthority<'info> {
+    // The owner, or the governance PDA signing through CPI
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"board", board.owner.as_ref(), board.board_id.to_le_bytes().as_ref()],
+        bump,
+        // The owner hands the board over once, after that only the authority can change it
+        constraint = board.authority.map_or(
+            admin.key() == board.owner,
+            |authority| authority == admin.key(),
+        ) @ ErrorTask::Unauthorized,
+    )]
+    pub board: Account<'info, Board>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct RenameBoard<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"board", board.owner.as_ref(), board.board_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = board.role_of(&admin.key()) == Some(Role::Admin) @ ErrorTask::Unauthorized,
+    )]
+    pub board: Account<'info, Board>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct StartTimer<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        mut,
+        seeds = [b"profile", task.author.as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct StopTimer<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        mut,
+        seeds = [b"profile", task.author.as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+#[instruction(duration_secs: u64, note: String)]
+pub struct LogWork<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.can_complete(&author.key()) @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        init,
+        payer = author,
+        space = 8 + WorkLog::INIT_SPACE,
+        seeds = [
+            b"work_log",
+            task.key().as_ref(),
+            author.key().as_ref(),
+            task.work_log_count.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub work_log: Account<'info, WorkLog>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct DeleteWorkLog<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [
+            b"work_log",
+            work_log.task.as_ref(),
+            author.key().as_ref(),
+            work_log.index.to_le_bytes().as_ref(),
+        ],
+        bump,
+        has_one = author @ ErrorTask::Unauthorized,
+        close = author,
+    )]
+    pub work_log: Account<'info, WorkLog>,
+}
+
+#[derive(Accounts)]
+pub struct RecordPomodoro<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        mut,
+        seeds = [b"profile", task.author.as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct SetEstimate<'info> {
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct SetCategory<'info> {
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct SetMetadataEntry<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveMetadataEntry<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct InitReactions<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Reactions::INIT_SPACE,
+        seeds = [b"reactions", task.key().as_ref()],
+        bump,
+    )]
+    pub reactions: Account<'info, Reactions>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct React<'info> {
+    pub reactor: Signer<'info>,
+    #[account(
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        mut,
+        seeds = [b"reactions", task.key().as_ref()],
+        bump,
+        has_one = task,
+    )]
+    pub reactions: Account<'info, Reactions>,
+    #[account(constraint = comment.task == task.key() @ ErrorTask::CommentMismatch)]
+    pub comment: Option<Account<'info, Comment>>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct WatchTask<'info> {
+    #[account(
+        mut,
+        constraint = wallet.key() != task.authority @ ErrorTask::CannotWatchOwnTask,
+    )]
+    pub wallet: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        init,
+        payer = wallet,
+        space = 8 + Watcher::INIT_SPACE,
+        seeds = [b"watcher", task.key().as_ref(), wallet.key().as_ref()],
+        bump,
+    )]
+    pub watcher: Account<'info, Watcher>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct UnwatchTask<'info> {
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        mut,
+        seeds = [b"watcher", task.key().as_ref(), wallet.key().as_ref()],
+        bump,
+        has_one = task,
+        has_one = wallet,
+        close = wallet,
+    )]
+    pub watcher: Account<'info, Watcher>,
+}
+
+#[derive(Accounts)]
+pub struct SetVisibility<'info> {
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+        constraint = task.authority == author.key() @ ErrorTask::Unauthorized,
+        constraint = task.deleted_at.is_none() @ ErrorTask::AlreadyTrashed,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(constraint = task.board == Some(board.key()) @ ErrorTask::BoardMismatch)]
+    pub board: Option<Account<'info, Board>>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct InitTaskTree<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    /// CHECK: zeroed account sized for the tree, initialized by the compression program
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = author,
+        space = 8 + TaskTree::INIT_SPACE,
+        seeds = [b"task_tree", merkle_tree.key().as_ref()],
+        bump,
+    )]
+    pub task_tree: Account<'info, TaskTree>,
+    pub log_wrapper: Program<'info, Noop>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateCompressedTask<'info> {
+    #[account(mut)]
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task_tree", merkle_tree.key().as_ref()],
+        bump = task_tree.bump,
+        has_one = author @ ErrorTask::Unauthorized,
+        has_one = merkle_tree,
+    )]
+    pub task_tree: Account<'info, TaskTree>,
+    #[account(
+        mut,
+        seeds = [b"profile", author.key().as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    // Lifts the free open-task cap while it's active
+    #[account(seeds = [b"subscription", profile.author.as_ref()], bump)]
+    pub subscription: Option<Account<'info, Subscription>>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump,
+    )]
+    pub treasury: SystemAccount<'info>,
+    /// CHECK: pinned by `has_one` on the task tree, validated by the compression program
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    pub log_wrapper: Program<'info, Noop>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ModifyTaskTree<'info> {
+    pub author: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task_tree", merkle_tree.key().as_ref()],
+        bump = task_tree.bump,
+        has_one = author @ ErrorTask::Unauthorized,
+        has_one = merkle_tree,
+    )]
+    pub task_tree: Account<'info, TaskTree>,
+    #[account(
+        mut,
+        seeds = [b"profile", author.key().as_ref()],
+        bump,
+    )]
+    pub profile: Account<'info, UserProfile>,
+    /// CHECK: pinned by `has_one` on the task tree, validated by the compression program
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    pub log_wrapper: Program<'info, Noop>,
+    pub compression_program: Program<'info, SplAccountCompression>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateTask<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: an old layout doesn't deserialize as `Task`, owner and discriminator are
+    /// checked in the handler
+    #[account(mut)]
+    pub task: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateRewardPool<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"board", board.owner.as_ref(), board.board_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = board.role_of(&admin.key()) == Some(Role::Admin) @ ErrorTask::Unauthorized,
+    )]
+    pub board: Account<'info, Board>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + RewardPool::INIT_SPACE,
+        seeds = [b"reward_pool", board.key().as_ref()],
+        bump,
+    )]
+    pub reward_pool: Account<'info, RewardPool>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct SetRewardRules<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [b"board", board.owner.as_ref(), board.board_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = board.role_of(&admin.key()) == Some(Role::Admin) @ ErrorTask::Unauthorized,
+    )]
+    pub board: Account<'info, Board>,
+    #[account(
+        mut,
+        seeds = [b"reward_pool", board.key().as_ref()],
+        bump,
+        has_one = board,
+    )]
+    pub reward_pool: Account<'info, RewardPool>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct FundRewardPool<'info> {
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"reward_pool", reward_pool.board.as_ref()],
+        bump,
+    )]
+    pub reward_pool: Account<'info, RewardPool>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct JoinRewardPool<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        seeds = [b"reward_pool", reward_pool.board.as_ref()],
+        bump,
+    )]
+    pub reward_pool: Account<'info, RewardPool>,
+    #[account(
+        init,
+        payer = user,
+        space = 8 + RewardTracker::INIT_SPACE,
+        seeds = [b"reward_tracker", reward_pool.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub reward_tracker: Account<'info, RewardTracker>,
+    pub system_program: Program<'info, System>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawRewards<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"reward_tracker", reward_tracker.pool.as_ref(), user.key().as_ref()],
+        bump,
+        has_one = user @ ErrorTask::Unauthorized,
+    )]
+    pub reward_tracker: Account<'info, RewardTracker>,
+}
+
+#[derive(Accounts)]
+pub struct RefundBounty<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        mut,
+        seeds = [b"escrow", task.key().as_ref()],
+        bump,
+        constraint = !escrow.disputed @ ErrorTask::BountyDisputed,
+        has_one = task,
+        has_one = funder @ ErrorTask::EscrowMismatch,
+        close = funder,
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct RefundBountySpl<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        mut,
+        seeds = [b"token_escrow", task.key().as_ref()],
+        bump,
+        has_one = task,
+        has_one = funder @ ErrorTask::EscrowMismatch,
+        has_one = mint @ ErrorTask::EscrowMismatch,
+        close = funder,
+    )]
+    pub token_escrow: Account<'info, TokenEscrow>,
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = task,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: InterfaceAccount<'info, token_interface::TokenAccount>,
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = funder,
+        token::token_program = token_program,
+    )]
+    pub funder_token_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[derive(Accounts)]
+pub struct SetArbiter<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        has_one = admin @ ErrorTask::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RaiseDispute<'info> {
+    pub party: Signer<'info>,
+    #[account(
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        mut,
+        seeds = [b"escrow", task.key().as_ref()],
+        bump,
+        has_one = task,
+        constraint = !escrow.disputed @ ErrorTask::BountyDisputed,
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    pub arbiter: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"task", task.author.as_ref(), task.task_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+    )]
+    pub task: Account<'info, Task>,
+    #[account(
+        mut,
+        seeds = [b"escrow", task.key().as_ref()],
+        bump,
+        has_one = task,
+        has_one = funder @ ErrorTask::EscrowMismatch,
+        constraint = escrow.disputed @ ErrorTask::NotDisputed,
+        close = funder,
+    )]
+    pub escrow: Account<'info, Escrow>,
+    /// CHECK: receives the funder's share and the escrow rent back, checked by `has_one` on
+    /// the escrow
+    #[account(mut)]
+    pub funder: UncheckedAccount<'info>,
+    /// CHECK: receives the worker's share, checked against the worker recorded on the escrow
+    #[account(
+        mut,
+        constraint = escrow.worker == Some(worker.key()) @ ErrorTask::NotAssignee,
+    )]
+    pub worker: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump,
+        constraint = config.arbiter == Some(arbiter.key()) @ ErrorTask::NotArbiter,
+        constraint = !config.paused @ ErrorTask::ProgramPaused,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct UserProfile {
+    pub author: Pubkey,
+    pub task_count: u64,
+    pub board_count: u64,
+    pub recurring_count: u64,
+    pub tasks_created: u64,
+    pub tasks_completed: u64,
+    pub tasks_deleted: u64,
+    // Tasks that are neither done nor cancelled
+    pub open_count: u64,
+    pub template_count: u64,
+    pub pinned_count: u32,
+    pub milestone_count: u64,
+    pub sprint_count: u64,
+    // Consecutive days with at least one completion, days are unix days (UTC)
+    pub current_streak: u32,
+    pub longest_streak: u32,
+    pub last_completion_day: i64,
+    pub xp: u64,
+    pub level: u8,
+    // The one task whose timer is running, timers never overlap
+    pub active_timer: Option<Pubkey>,
+    // Pomodoros finished on `pomodoro_day` (unix day), reset on the first one of a new day
+    pub pomodoros_today: u32,
+    pub pomodoro_day: i64,
+}
+
+pub enum StreakChange {
+    Unchanged,
+    Extended,
+    // Carries the streak that was lost
+    Broken(u32),
+}
+
+impl UserProfile {
+    pub const MAX_PINNED: u32 = 10;
+
+    // Authors past this many completions earn a badge on any task
+    pub const BADGE_MIN_COMPLETIONS: u64 = 10;
+
+    pub fn record_created(&mut self) {
+        self.tasks_created = self.tasks_created.saturating_add(1);
+        self.open_count = self.open_count.saturating_add(1);
+    }
+
+    pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+    // XP needed to reach level 1, 2, ... in order
+    pub const LEVEL_THRESHOLDS: [u64; 10] =
+        [100, 250, 500, 1000, 2000, 3500, 5500, 8000, 12000, 20000];
+
+    pub fn level_for(xp: u64) -> u8 {
+        Self::LEVEL_THRESHOLDS
+            .iter()
+            .take_while(|threshold| xp >= **threshold)
+            .count() as u8
+    }
+
+    pub fn record_completed(&mut self, now: i64) -> StreakChange {
+        self.tasks_completed = self.tasks_completed.saturating_add(1);
+        self.open_count = self.open_count.saturating_sub(1);
+
+        let day = now.div_euclid(Self::SECONDS_PER_DAY);
+        let change = if self.current_streak > 0 && day == self.last_completion_day {
+            StreakChange::Unchanged
+        } else if self.current_streak > 0 && day == self.last_completion_day + 1 {
+            self.current_streak = self.current_streak.saturating_add(1);
+            StreakChange::Extended
+        } else {
+            let previous = self.current_streak;
+            self.current_streak = 1;
+            if previous > 0 {
+                StreakChange::Broken(previous)
+            } else {
+                StreakChange::Extended
+            }
+        };
+        self.longest_streak = self.longest_streak.max(self.current_streak);
+        self.last_completion_day = day;
+        change
+    }
+
+    pub fn record_pomodoro(&mut self, now: i64) {
+        let day = now.div_euclid(Self::SECONDS_PER_DAY);
+        if day != self.pomodoro_day {
+            self.pomodoro_day = day;
+            self.pomodoros_today = 0;
+        }
+        self.pomodoros_today = self.pomodoros_today.saturating_add(1);
+    }
+
+    pub fn record_reopened(&mut self) {
+        self.open_count = self.open_count.saturating_add(1);
+    }
+
+    pub fn record_recompleted(&mut self) {
+        self.open_count = self.open_count.saturating_sub(1);
+    }
+
+    pub fn record_cancelled(&mut self) {
+        self.open_count = self.open_count.saturating_sub(1);
+    }
+
+    // Compressed tasks only hold an open slot, they don't feed the stats or the streak
+    pub fn record_compressed_completed(&mut self) {
+        self.open_count = self.open_count.saturating_sub(1);
+    }
+
+    pub fn record_expired(&mut self) {
+        self.open_count = self.open_count.saturating_sub(1);
+    }
+
+    pub fn record_trashed(&mut self) {
+        self.open_count = self.open_count.saturating_sub(1);
+    }
+
+    pub fn record_restored(&mut self) {
+        self.open_count = self.open_count.saturating_add(1);
+    }
+
+    pub fn record_deleted(&mut self, was_open: bool) {
+        self.tasks_deleted = self.tasks_deleted.saturating_add(1);
+        if was_open {
+            self.open_count = self.open_count.saturating_sub(1);
+        }
+    }
+}
+
+// Ids of the author's tasks in creation order so clients can page through them
+// without scanning program accounts, large tasks aren't tracked
+#[account]
+#[derive(InitSpace)]
+pub struct TaskIndex {
+    pub author: Pubkey,
+    #[max_len(256)]
+    pub task_ids: Vec<u64>,
+}
+
+impl TaskIndex {
+    // Ids the account is created with room for, a full index grows by `GROWTH` more
+    pub const MAX_TASKS: usize = 256;
+    pub const GROWTH: usize = 64;
+
+    pub fn remove(&mut self, task_id: u64) {
+        self.task_ids.retain(|id| *id != task_id);
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub admin: Pubkey,
+    pub max_title_len: u32,
+    pub max_description_len: u32,
+    // Cap on a profile's open tasks, 0 leaves it uncapped
+    pub max_tasks_per_user: u64,
+    pub paused: bool,
+    // Lamports charged into the treasury for every new task, 0 disables it
+    pub creation_fee: u64,
+    // Seconds past the due date before anyone can expire a task
+    pub expiry_grace: i64,
+    // Lamports paid to the cranker out of the task's bounty escrow on expiry
+    pub expiry_tip: u64,
+    // Bubblegum tree compressed badges are minted into, delegated to the badge authority PDA
+    pub badge_tree: Option<Pubkey>,
+    // Times a single task can be snoozed, 0 leaves it uncapped
+    pub max_snoozes: u32,
+    // Mints `fund_task_spl` accepts, empty allows any mint
+    #[max_len(8)]
+    pub allowed_mints: Vec<Pubkey>,
+    // Lamports per `Subscription::PERIOD` for each paid tier, 0 means it can't be bought
+    pub tier_prices: [u64; 2],
+    // Open-task caps for each paid tier, 0 leaves it uncapped
+    pub tier_task_limits: [u64; 2],
+    // Switchboard feed whose update time new stakes are slashed against instead of the clock
+    pub time_oracle: Option<Pubkey>,
+    // Settles disputed bounties, disputes can't be raised while it's unset
+    pub arbiter: Option<Pubkey>,
+}
+
+impl Config {
+    pub const MAX_ALLOWED_MINTS: usize = 8;
+    // Hard ceilings on the configurable limits, counted in characters
+    pub const TITLE_LEN_CAP: u32 = 128;
+    pub const DESCRIPTION_LEN_CAP: u32 = 5000;
+    // Byte bounds the accounts are sized for, so a `Task` always fits a single realloc
+    // and a `RecurringTask` a single `init` whatever the characters encode to
+    pub const TITLE_BYTE_CAP: usize = 256;
+    pub const DESCRIPTION_BYTE_CAP: usize = 5000;
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct GlobalStats {
+    pub total_tasks: u64,
+    pub total_completed: u64,
+    pub unique_authors: u64,
+}
+
+impl GlobalStats {
+    pub fn record_created(&mut self) {
+        self.total_tasks = self.total_tasks.saturating_add(1);
+    }
+
+    pub fn record_completed(&mut self) {
+        self.total_completed = self.total_completed.saturating_add(1);
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Reputation {
+    pub assignee: Pubkey,
+    // Completed tasks the author accepted
+    pub completions: u64,
+    // Of those, the ones done by their due date or without one
+    pub on_time: u64,
+}
+
+impl Reputation {
+    pub fn on_time_rate_bps(&self) -> u64 {
+        if self.completions == 0 {
+            return 0;
+        }
+        self.on_time.saturating_mul(10_000) / self.completions
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Achievement {
+    pub achievement_id: u64,
+    #[max_len(32)]
+    pub name: String,
+    pub criteria: AchievementCriteria,
+    pub threshold: u64,
+    // XP added to the profile on unlock
+    pub reward_xp: u64,
+}
+
+impl Achievement {
+    pub fn is_met(&self, profile: &UserProfile) -> bool {
+        let value = match self.criteria {
+            AchievementCriteria::TasksCreated => profile.tasks_created,
+            AchievementCriteria::TasksCompleted => profile.tasks_completed,
+            AchievementCriteria::LongestStreak => profile.longest_streak as u64,
+            AchievementCriteria::Level => profile.level as u64,
+            AchievementCriteria::Xp => profile.xp,
+        };
+        value >= self.threshold
+    }
+}
+
+// Which profile counter an achievement's threshold is checked against
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum AchievementCriteria {
+    TasksCreated,
+    TasksCompleted,
+    LongestStreak,
+    Level,
+    Xp,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct AchievementUnlock {
+    pub author: Pubkey,
+    pub achievement: Pubkey,
+    pub unlocked_at: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct LeaderboardEntry {
+    pub author: Pubkey,
+    pub score: u64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Leaderboard {
+    // Sorted by score, highest first
+    #[max_len(20)]
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+impl Leaderboard {
+    pub const SIZE: usize = 20;
+
+    // Returns the author's zero based rank, None when the score didn't make the board
+    pub fn submit(&mut self, author: Pubkey, score: u64) -> Option<usize> {
+        self.entries.retain(|entry| entry.author != author);
+        let rank = self
+            .entries
+            .iter()
+            .position(|entry| entry.score < score)
+            .unwrap_or(self.entries.len());
+        if rank >= Self::SIZE {
+            return None;
+        }
+        self.entries
+            .insert(rank, LeaderboardEntry { author, score });
+        self.entries.truncate(Self::SIZE);
+        Some(rank)
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Board {
+    pub owner: Pubkey,
+    pub board_id: u64,
+    #[max_len(50)]
+    pub name: String,
+    pub task_count: u64,
+    #[max_len(10)]
+    pub members: Vec<Member>,
+    // A governance PDA (e.g. an SPL Governance realm's native treasury) that signs via CPI
+    // and acts as an admin next to the owner
+    pub authority: Option<Pubkey>,
+}
+
+impl Board {
+    // The owner and the governance authority are always admins, everyone else needs a
+    // member entry
+    pub fn role_of(&self, key: &Pubkey) -> Option<Role> {
+        if *key == self.owner || self.authority.as_ref() == Some(key) {
+            return Some(Role::Admin);
+        }
+        self.members
+            .iter()
+            .find(|m| m.pubkey == *key)
+            .map(|m| m.role)
+    }
+
+    pub fn can_edit(&self, key: &Pubkey) -> bool {
+        self.role_of(key) >= Some(Role::Editor)
+    }
+}
+
+// Ring buffer of the board's latest actions, `next` is the slot the next entry overwrites
+// once `entries` is full
+#[account]
+#[derive(InitSpace)]
+pub struct ActivityLog {
+    pub board: Pubkey,
+    #[max_len(32)]
+    pub entries: Vec<ActivityEntry>,
+    pub next: u16,
+    pub total: u64,
+}
+
+impl ActivityLog {
+    pub const CAPACITY: usize = 32;
+
+    pub fn record(&mut self, actor: Pubkey, action: Activity, target: Pubkey, slot: u64) {
+        let entry = ActivityEntry {
+            actor,
+            action,
+            target,
+            slot,
+        };
+        if self.entries.len() < Self::CAPACITY {
+            self.entries.push(entry);
+        } else {
+            self.entries[self.next as usize] = entry;
+        }
+        self.next = ((self.next as usize + 1) % Self::CAPACITY) as u16;
+        self.total = self.total.saturating_add(1);
+    }
+}
+
+// `target` is the task for task actions and the member's key for membership changes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
+pub struct ActivityEntry {
+    pub actor: Pubkey,
+    pub action: Activity,
+    pub target: Pubkey,
+    pub slot: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum Activity {
+    Created,
+    Updated,
+    Completed,
+    Reopened,
+    StatusChanged,
+    Deleted,
+    MemberAdded,
+    MemberRemoved,
+    RoleChanged,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
+pub struct Member {
+    pub pubkey: Pubkey,
+    pub role: Role,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Invite {
+    pub board: Pubkey,
+    pub member: Pubkey,
+    pub role: Role,
+    pub inviter: Pubkey,
+    pub created_at: i64,
+}
+
+// Ordered so that a higher role includes everything a lower one may do
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, PartialOrd, Debug, InitSpace,
+)]
+pub enum Role {
+    Viewer,
+    Editor,
+    Admin,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Task {
+    // Fixed-size fields come first so each sits at a stable offset for memcmp filters,
+    // see the `*_OFFSET` constants
+    pub author: Pubkey,
+    pub task_id: u64,
+    pub priority: Priority,
+    pub status: TaskStatus,
+    pub is_archived: bool,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub reopened_count: u32,
+    pub comment_count: u64,
+    pub assignment_accepted: bool,
+    pub has_bounty: bool,
+    pub has_token_bounty: bool,
+    pub has_stake: bool,
+    pub badge_minted: bool,
+    pub reputation_recorded: bool,
+    // 0 to 100, follows the checklist when it changes, set directly with `set_progress`
+    // and pinned to 100 once the task is done
+    pub progress: u8,
+    // Description holds base64 ciphertext instead of plain text
+    pub is_private: bool,
+    // Canonical bump saved at creation so later contexts skip the bump search
+    pub bump: u8,
+    // Position picked by the client for manual ordering, ties fall back to `created_at`
+    pub sort_order: u32,
+    pub is_pinned: bool,
+    pub snooze_count: u32,
+    pub revision_count: u64,
+    // Last of the fixed-offset fields since `Other` makes it one byte longer, filter on its
+    // Borsh encoding at `CATEGORY_OFFSET`
+    pub category: Category,
+    #[max_len(100)]
+    pub title: String,
+    #[max_len(1000)]
+    pub description: String,
+    pub due_date: Option<i64>,
+    pub completed_at: Option<i64>,
+    #[max_len(5, 20)]
+    pub tags: Vec<String>,
+    pub parent: Option<Pubkey>,
+    #[max_len(10)]
+    pub blocked_by: Vec<Pubkey>,
+    pub board: Option<Pubkey>,
+    pub assignee: Option<Pubkey>,
+    pub pending_author: Option<Pubkey>,
+    #[max_len(10)]
+    pub checklist: Vec<ChecklistItem>,
+    #[max_len(5)]
+    pub attachments: Vec<Attachment>,
+    // Set for hash-only tasks, the body lives off-chain and `description` holds its uri
+    pub content_hash: Option<[u8; 32]>,
+    pub milestone: Option<Pubkey>,
+    pub sprint: Option<Pubkey>,
+    #[max_len(5)]
+    pub labels: Vec<Pubkey>,
+    // Set while the task sits in the trash, `purge_task` closes it after `TRASH_GRACE`
+    pub deleted_at: Option<i64>,
+    // Set when completions have to be signed off, see `approve_completion`
+    pub reviewer: Option<Pubkey>,
+    // Board members whose approval `approve_task` needs before the task completes, 0 = off
+    pub required_approvals: u8,
+    // The owner plus up to 10 members
+    #[max_len(11)]
+    pub approvals: Vec<Pubkey>,
+    // Who may mutate the task, the author unless handed to a multisig or other PDA with
+    // `set_task_authority`. Seeds, profile and counters stay with `author`
+    pub authority: Pubkey,
+    // Set while a timer runs, `stop_timer` folds it into `total_seconds_spent`
+    pub timer_started_at: Option<i64>,
+    pub total_seconds_spent: u64,
+    // Next `WorkLog` index, never reused
+    pub work_log_count: u64,
+    pub pomodoros_completed: u32,
+    pub estimate_seconds: Option<u64>,
+    // Free-form entries for integrators (ticket ids, external refs), keys are unique
+    #[max_len(5)]
+    pub metadata: Vec<MetadataEntry>,
+    pub watcher_count: u32,
+    pub visibility: Visibility,
+    // Layout the account was written with, `migrate_task` brings older ones up to date
+    pub version: u8,
+    // Set the first time the task is completed, reopening never earns the credit again
+    pub rewarded: bool,
+    // Set once a reward pool has paid for the task, so a reopen can't collect it again
+    pub reward_paid: bool,
+    // Tasks naming this one as `parent` or in `blocked_by`, they are passed this task when
+    // they drop the link or close
+    pub dependent_count: u32,
+}
+
+// The last layout without `version`. Its Vecs were allocated at full size, so bytes a
+// shrunk Vec left behind sit where the newer fields go, and an old account has to be read
+// field by field through this instead of as a zero-extended `Task`
+#[derive(AnchorDeserialize)]
+struct TaskV0 {
+    author: Pubkey,
+    task_id: u64,
+    priority: Priority,
+    status: TaskStatus,
+    is_archived: bool,
+    created_at: i64,
+    updated_at: i64,
+    reopened_count: u32,
+    comment_count: u64,
+    assignment_accepted: bool,
+    has_bounty: bool,
+    has_token_bounty: bool,
+    has_stake: bool,
+    badge_minted: bool,
+    reputation_recorded: bool,
+    progress: u8,
+    is_private: bool,
+    bump: u8,
+    sort_order: u32,
+    is_pinned: bool,
+    snooze_count: u32,
+    revision_count: u64,
+    category: Category,
+    title: String,
+    description: String,
+    due_date: Option<i64>,
+    completed_at: Option<i64>,
+    tags: Vec<String>,
+    parent: Option<Pubkey>,
+    blocked_by: Vec<Pubkey>,
+    board: Option<Pubkey>,
+    assignee: Option<Pubkey>,
+    pending_author: Option<Pubkey>,
+    checklist: Vec<ChecklistItem>,
+    attachments: Vec<Attachment>,
+    content_hash: Option<[u8; 32]>,
+    milestone: Option<Pubkey>,
+    sprint: Option<Pubkey>,
+    labels: Vec<Pubkey>,
+    deleted_at: Option<i64>,
+    reviewer: Option<Pubkey>,
+    required_approvals: u8,
+    approvals: Vec<Pubkey>,
+    authority: Pubkey,
+    timer_started_at: Option<i64>,
+    total_seconds_spent: u64,
+    work_log_count: u64,
+    pomodoros_completed: u32,
+    estimate_seconds: Option<u64>,
+    metadata: Vec<MetadataEntry>,
+    watcher_count: u32,
+    visibility: Visibility,
+}
+
+impl From<TaskV0> for Task {
+    fn from(old: TaskV0) -> Self {
+        Task {
+            author: old.author,
+            task_id: old.task_id,
+            priority: old.priority,
+            status: old.status,
+            is_archived: old.is_archived,
+            created_at: old.created_at,
+            updated_at: old.updated_at,
+            reopened_count: old.reopened_count,
+            comment_count: old.comment_count,
+            assignment_accepted: old.assignment_accepted,
+            has_bounty: old.has_bounty,
+            has_token_bounty: old.has_token_bounty,
+            has_stake: old.has_stake,
+            badge_minted: old.badge_minted,
+            reputation_recorded: old.reputation_recorded,
+            progress: old.progress,
+            is_private: old.is_private,
+            bump: old.bump,
+            sort_order: old.sort_order,
+            is_pinned: old.is_pinned,
+            snooze_count: old.snooze_count,
+            revision_count: old.revision_count,
+            category: old.category,
+            title: old.title,
+            description: old.description,
+            due_date: old.due_date,
+            completed_at: old.completed_at,
+            tags: old.tags,
+            parent: old.parent,
+            blocked_by: old.blocked_by,
+            board: old.board,
+            assignee: old.assignee,
+            pending_author: old.pending_author,
+            checklist: old.checklist,
+            attachments: old.attachments,
+            content_hash: old.content_hash,
+            milestone: old.milestone,
+            sprint: old.sprint,
+            labels: old.labels,
+            deleted_at: old.deleted_at,
+            reviewer: old.reviewer,
+            required_approvals: old.required_approvals,
+            approvals: old.approvals,
+            // Accounts from before `set_task_authority` hold a zeroed authority
+            authority: if old.authority == Pubkey::default() {
+                old.author
+            } else {
+                old.authority
+            },
+            timer_started_at: old.timer_started_at,
+            total_seconds_spent: old.total_seconds_spent,
+            work_log_count: old.work_log_count,
+            pomodoros_completed: old.pomodoros_completed,
+            estimate_seconds: old.estimate_seconds,
+            metadata: old.metadata,
+            watcher_count: old.watcher_count,
+            visibility: old.visibility,
+            version: Task::VERSION,
+            // A done or reopened task already earned its completion credit
+            rewarded: old.completed_at.is_some() || old.reopened_count > 0,
+            reward_paid: false,
+            // Counted back up as each dependent is migrated, see `migrate_task`
+            dependent_count: 0,
+        }
+    }
+}
+
+impl Task {
+    // Byte offsets into the account data, discriminator included
+    pub const AUTHOR_OFFSET: usize = 8;
+    pub const TASK_ID_OFFSET: usize = Task::AUTHOR_OFFSET + 32;
+    pub const PRIORITY_OFFSET: usize = Task::TASK_ID_OFFSET + 8;
+    pub const STATUS_OFFSET: usize = Task::PRIORITY_OFFSET + 1;
+    pub const IS_ARCHIVED_OFFSET: usize = Task::STATUS_OFFSET + 1;
+    pub const CREATED_AT_OFFSET: usize = Task::IS_ARCHIVED_OFFSET + 1;
+    pub const UPDATED_AT_OFFSET: usize = Task::CREATED_AT_OFFSET + 8;
+    // Past updated_at, the counters, flags, progress, bump, sort order, pin and snooze and
+    // revision counts
+    pub const CATEGORY_OFFSET: usize = Task::UPDATED_AT_OFFSET + 8 + 4 + 8 + 6 + 3 + 4 + 1 + 4 + 8;
+
+    // Seconds a trashed task can still be restored
+    pub const TRASH_GRACE: i64 = 30 * 24 * 60 * 60;
+
+    // A week between creation and completion earns a badge
+    pub const BADGE_MIN_DURATION: i64 = 7 * 24 * 60 * 60;
+
+    // Spare bytes so small edits don't need a realloc
+    pub const SPACE_HEADROOM: usize = 32;
+
+    // Bumped whenever fields are added, accounts from before `version` existed are `TaskV0`
+    pub const VERSION: u8 = 1;
+
+    // Space for a task holding these strings, every other field is reserved at its max
+    pub fn space(title: &str, description: &str) -> usize {
+        8 + Task::INIT_SPACE - 100 - 1000 + title.len() + description.len() + Task::SPACE_HEADROOM
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn init(
+        &mut self,
+        author: Pubkey,
+        task_id: u64,
+        title: String,
+        description: String,
+        due_date: Option<i64>,
+        priority: Priority,
+        now: i64,
+    ) {
+        self.author = author;
+        self.task_id = task_id;
+        self.priority = priority;
+        self.title = title;
+        self.description = description;
+        self.status = TaskStatus::Todo;
+        self.created_at = now;
+        self.due_date = due_date;
+        self.reopened_count = 0;
+        self.tags = Vec::new();
+        self.parent = None;
+        self.blocked_by = Vec::new();
+        self.board = None;
+        self.assignee = None;
+        self.assignment_accepted = false;
+        self.comment_count = 0;
+        self.has_bounty = false;
+        self.has_token_bounty = false;
+        self.has_stake = false;
+        self.updated_at = now;
+        self.completed_at = None;
+        self.is_archived = false;
+        self.pending_author = None;
+        self.badge_minted = false;
+        self.reputation_recorded = false;
+        self.checklist = Vec::new();
+        self.progress = 0;
+        self.attachments = Vec::new();
+        self.is_private = false;
+        self.content_hash = None;
+        // Filled in from `ctx.bumps` by the creating instruction
+        self.bump = 0;
+        self.sort_order = 0;
+        self.is_pinned = false;
+        self.snooze_count = 0;
+        self.revision_count = 0;
+        self.milestone = None;
+        self.sprint = None;
+        self.labels = Vec::new();
+        self.deleted_at = None;
+        self.reviewer = None;
+        self.required_approvals = 0;
+        self.approvals = Vec::new();
+        self.authority = author;
+        self.timer_started_at = None;
+        self.total_seconds_spent = 0;
+        self.work_log_count = 0;
+        self.pomodoros_completed = 0;
+        self.estimate_seconds = None;
+        self.category = Category::Other(0);
+        self.metadata = Vec::new();
+        self.watcher_count = 0;
+        self.visibility = Visibility::Public;
+        self.version = Task::VERSION;
+        self.rewarded = false;
+        self.reward_paid = false;
+        self.dependent_count = 0;
+    }
+
+    // Returns the seconds the stopped timer ran, `None` when none was running
+    pub fn stop_timer(&mut self, now: i64) -> Option<u64> {
+        let started = self.timer_started_at.take()?;
+        let elapsed = now.saturating_sub(started).max(0) as u64;
+        self.total_seconds_spent = self.total_seconds_spent.saturating_add(elapsed);
+        Some(elapsed)
+    }
+
+    pub fn can_complete(&self, signer: &Pubkey) -> bool {
+        *signer == self.authority
+            || (self.assignment_accepted && self.assignee.as_ref() == Some(signer))
+    }
+
+    pub fn update_progress(&mut self) {
+        if self.is_completed() {
+            self.progress = 100;
+            return;
+        }
+        let done = self.checklist.iter().filter(|item| item.done).count();
+        self.progress = if self.checklist.is_empty() {
+            0
+        } else {
+            (done * 100 / self.checklist.len()) as u8
+        };
+    }
+
+    pub fn is_completed(&self) -> bool {
+        self.status == TaskStatus::Done
+    }
+
+    pub fn is_open(&self) -> bool {
+        !matches!(
+            self.status,
+            TaskStatus::Done | TaskStatus::Cancelled | TaskStatus::Expired
+        )
+    }
+
+    pub fn is_overdue(&self, now: i64) -> bool {
+        self.due_date
+            .is_some_and(|due| !self.is_completed() && now > due)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum TaskStatus {
+    Todo,
+    InProgress,
+    Blocked,
+    Done,
+    Cancelled,
+    // Only set by `expire_task`
+    Expired,
+    // Only set by `complete_task` on a task with a reviewer
+    PendingReview,
+}
+
+impl TaskStatus {
+    // Done, Cancelled and Expired are terminal, everything else can move freely between the
+    // open states. A task in review is either approved or sent back to InProgress
+    pub fn can_transition_to(&self, next: TaskStatus) -> bool {
+        use TaskStatus::*;
+        matches!(
+            (*self, next),
+            (
+                Todo,
+                InProgress | Blocked | Done | Cancelled | PendingReview
+            ) | (
+                InProgress,
+                Todo | Blocked | Done | Cancelled | PendingReview
+            ) | (Blocked, Todo | InProgress | Cancelled)
+                | (PendingReview, Done | InProgress)
+        )
+    }
+}
+
+// Maps the old `is_completed` flag onto the status it stood for
+impl From<bool> for TaskStatus {
+    fn from(is_completed: bool) -> Self {
+        if is_completed {
+            TaskStatus::Done
+        } else {
+            TaskStatus::Todo
+        }
+    }
+}
+
+// `Other(0)` is uncategorized, the rest of `Other` is free for clients to assign
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum Category {
+    Work,
+    Personal,
+    Errand,
+    Health,
+    Other(u8),
+}
+
+// Who besides the task's authority, author and assignee can comment, react and claim
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum Visibility {
+    Public,
+    Private,
+    BoardOnly,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NotificationKind {
+    Assigned,
+    Mentioned,
+    ReviewRequested,
+    Approved,
+    ChangesRequested,
+    DueSoon,
+    Overdue,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum Priority {
+    Low,
+    Medium,
+    High,
+    Urgent,
+}
+
+impl Priority {
+    pub fn xp(self) -> u64 {
+        match self {
+            Priority::Low => 10,
+            Priority::Medium => 20,
+            Priority::High => 40,
+            Priority::Urgent => 80,
+        }
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Milestone {
+    pub author: Pubkey,
+    pub milestone_id: u64,
+    #[max_len(50)]
+    pub name: String,
+    pub target_date: i64,
+    // Linked tasks, and how many of them are done
+    pub total: u64,
+    pub completed: u64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Sprint {
+    pub author: Pubkey,
+    pub sprint_id: u64,
+    #[max_len(50)]
+    pub name: String,
+    pub start_at: i64,
+    pub end_at: i64,
+    pub is_closed: bool,
+    pub task_count: u64,
+    // Recorded by `close_sprint`
+    pub completed_count: u64,
+}
+
+impl Sprint {
+    // Tasks can join until the sprint ends or is closed
+    pub fn is_open(&self, now: i64) -> bool {
+        !self.is_closed && now <= self.end_at
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Label {
+    pub author: Pubkey,
+    // Also the PDA seed, so at most 32 bytes
+    #[max_len(32)]
+    pub name: String,
+    // RGB
+    pub color: [u8; 3],
+    // Tasks the label is applied to
+    pub usage_count: u64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct TaskRevision {
+    pub task: Pubkey,
+    pub revision_id: u64,
+    // sha256 of the description this edit replaced
+    pub previous_description_hash: [u8; 32],
+    pub editor: Pubkey,
+    pub edited_at: i64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Template {
+    pub author: Pubkey,
+    pub template_id: u64,
+    #[max_len(128)]
+    pub title_pattern: String,
+    #[max_len(1000)]
+    pub description: String,
+    pub priority: Priority,
+    #[max_len(5, 20)]
+    pub tags: Vec<String>,
+    // Tasks spawned so far, the next one is numbered `use_count + 1`
+    pub use_count: u64,
+}
+
+impl Template {
+    pub const MAX_TITLE_PATTERN_LEN: usize = 128;
+    pub const MAX_DESCRIPTION_LEN: usize = 1000;
+
+    pub fn title_for(&self, n: u64) -> String {
+        self.title_pattern.replace("{n}", &n.to_string())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct WorkLog {
+    pub task: Pubkey,
+    pub author: Pubkey,
+    pub index: u64,
+    pub duration_secs: u64,
+    #[max_len(200)]
+    pub note: String,
+    pub logged_at: i64,
+}
+
+// One entry of a `create_tasks` batch, same arguments as `create_task`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct TaskInput {
+    pub title: String,
+    pub description: String,
+    pub due_date: Option<i64>,
+    pub priority: Priority,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
+pub struct ChecklistItem {
+    #[max_len(50)]
+    pub text: String,
+    pub done: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
+pub struct MetadataEntry {
+    #[max_len(32)]
+    pub key: String,
+    #[max_len(64)]
+    pub value: String,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
+pub struct Attachment {
+    pub sha256: [u8; 32],
+    #[max_len(200)]
+    pub uri: String,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Comment {
+    pub task: Pubkey,
+    pub author: Pubkey,
+    pub comment_id: u64,
+    #[max_len(500)]
+    pub body: String,
+    pub created_at: i64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Reactions {
+    pub task: Pubkey,
+    #[max_len(32)]
+    pub entries: Vec<Reaction>,
+}
+
+impl Reactions {
+    pub const CAPACITY: usize = 32;
+}
+
+// `comment_id` is `None` for a reaction on the task itself
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug, InitSpace)]
+pub struct Reaction {
+    pub reactor: Pubkey,
+    pub emoji_code: u32,
+    pub comment_id: Option<u64>,
+}
+
+// Indexers list a task's watchers by its `WatchedStatusChanged` events and these accounts
+#[account]
+#[derive(InitSpace)]
+pub struct Watcher {
+    pub task: Pubkey,
+    pub wallet: Pubkey,
+    pub created_at: i64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct TaskTree {
+    pub author: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub leaf_count: u64,
+    pub bump: u8,
+}
+
+// The leaf is the hash of this struct, indexers rebuild it from `CompressedTaskChanged`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct CompressedTask {
+    pub author: Pubkey,
+    pub index: u32,
+    pub title: String,
+    pub priority: Priority,
+    pub status: TaskStatus,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub completed_at: Option<i64>,
+}
+
+impl CompressedTask {
+    pub fn leaf(&self) -> Result<[u8; 32]> {
+        Ok(hash(&self.try_to_vec()?).to_bytes())
+    }
+}
+
+// Lamports above rent are what sponsors have left to pay out
+#[account]
+#[derive(InitSpace)]
+pub struct RewardPool {
+    pub board: Pubkey,
+    pub reward_per_task: u64,
+    pub daily_cap: u32,
+    pub total_deposited: u64,
+    pub total_paid: u64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct RewardTracker {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub day: i64,
+    pub rewards_today: u32,
+    pub total_earned: u64,
+}
+
+impl RewardTracker {
+    // Starts the count over on a new day, false once today's cap is used up
+    pub fn can_earn(&mut self, now: i64, daily_cap: u32) -> bool {
+        let day = now.div_euclid(UserProfile::SECONDS_PER_DAY);
+        if day != self.day {
+            self.day = day;
+            self.rewards_today = 0;
+        }
+        self.rewards_today < daily_cap
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Escrow {
+    pub task: Pubkey,
+    pub funder: Pubkey,
+    pub amount: u64,
+    // Bounty in micro-USD, paid out in SOL at the claim-time price with `amount` as the cap
+    pub usd_amount: Option<u64>,
+    // The task's due date when it was funded, past it an unfinished bounty can be refunded
+    pub deadline: Option<i64>,
+    // Frozen until the arbiter resolves it, neither claim nor refund goes through meanwhile
+    pub disputed: bool,
+    // The accepted assignee when the dispute was raised, the arbiter's split pays this key
+    pub worker: Option<Pubkey>,
+}
+
+impl Escrow {
+    pub const SOL_USD_FEED_ID: &'static str =
+        "0xef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d";
+    // Seconds a price may be old, and its confidence interval in bps of the price
+    pub const MAX_PRICE_AGE: u64 = 60;
+    pub const MAX_CONFIDENCE_BPS: u64 = 200;
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct TokenEscrow {
+    pub task: Pubkey,
+    pub funder: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    // The task's due date when it was funded, past it an unfinished bounty can be refunded
+    pub deadline: Option<i64>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Stake {
+    pub task: Pubkey,
+    pub author: Pubkey,
+    pub amount: u64,
+    pub deadline: i64,
+    // Fixed from the config at stake time so changing the config can't move the goalposts
+    pub oracle: Option<Pubkey>,
+}
+
+// Fixed-size layout so the 10KB description is read in place instead of copied onto
+// the heap, the whole account stays under the 10240 byte limit for `init`
+#[account(zero_copy)]
+pub struct TaskLarge {
+    pub author: Pubkey,
+    pub task_id: u64,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub description_len: u16,
+    pub title_len: u8,
+    // `TaskStatus` as u8
+    pub status: u8,
+    pub _padding: [u8; 4],
+    pub title: [u8; 128],
+    pub description: [u8; 10_000],
+}
+
+impl TaskLarge {
+    pub const MAX_TITLE_LEN: usize = 128;
+    pub const MAX_DESCRIPTION_LEN: usize = 10_000;
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct RecurringTask {
+    pub author: Pubkey,
+    pub recurring_id: u64,
+    #[max_len(256)]
+    pub title: String,
+    #[max_len(5000)]
+    pub description: String,
+    // Seconds between instances
+    pub interval: i64,
+    pub next_due: i64,
+    pub spawned_count: u64,
+}
+
+impl RecurringTask {
+    pub fn space(title: &str, description: &str) -> usize {
+        8 + RecurringTask::INIT_SPACE - 128 - 5000 + title.len() + description.len()
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Subscription {
+    pub author: Pubkey,
+    pub tier: Tier,
+    pub expires_at: i64,
+}
+
+impl Subscription {
+    pub const PERIOD: i64 = 30 * 24 * 60 * 60;
+}
+
+// Paid tiers, a profile without an active subscription is on the free limits
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, InitSpace)]
+pub enum Tier {
+    Pro,
+    Team,
+}
+
+// A session key allowed to act for `owner`, seeded by both keys so instructions can
+// derive it from the task's authority and the signer
+#[account]
+#[derive(InitSpace)]
+pub struct Delegate {
+    pub owner: Pubkey,
+    pub session_key: Pubkey,
+    pub expires_at: i64,
+    pub actions: u8,
+}
+
+impl Delegate {
+    pub const COMPLETE: u8 = 1 << 0;
+    pub const UPDATE: u8 = 1 << 1;
+    pub const ALL: u8 = Self::COMPLETE | Self::UPDATE;
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Reminder {
+    pub author: Pubkey,
+    // Seconds before the due date a task counts as due soon
+    pub due_soon_window: i64,
+    pub thread: Option<Pubkey>,
+}
+
+// Tasks on a board can only be changed by a signer who is an editor there
+fn require_board_editor(
+    task: &Task,
+    board: Option<&Account<Board>>,
+    signer: &Pubkey,
+) -> Result<()> {
+    if task.board.is_some() {
+        let board = board.ok_or(ErrorTask::BoardMismatch)?;
+        require!(board.can_edit(signer), ErrorTask::NotBoardEditor);
+    }
+    Ok(())
+}
+
+// The board is only needed when a board-only task is written to by someone outside the
+// task itself
+fn require_visible_to(task: &Task, board: Option<&Account<Board>>, wallet: &Pubkey) -> Result<()> {
+    if *wallet == task.authority || *wallet == task.author || task.assignee.as_ref() == Some(wallet)
+    {
+        return Ok(());
+    }
+    match task.visibility {
+        Visibility::Public => Ok(()),
+        Visibility::Private => err!(ErrorTask::TaskNotVisible),
+        Visibility::BoardOnly => {
+            let board = board.ok_or(ErrorTask::BoardMismatch)?;
+            require!(board.role_of(wallet).is_some(), ErrorTask::TaskNotVisible);
+            Ok(())
+        }
+    }
+}
+
+fn validate_title(title: &str, config: &Config) -> Result<()> {
+    require!(
+        title.len() <= Config::TITLE_BYTE_CAP,
+        ErrorTask::TitleTooManyBytes
+    );
+    require!(
+        title.chars().count() <= config.max_title_len as usize,
+        ErrorTask::TitleTooManyChars
+    );
+    require!(!title.trim().is_empty(), ErrorTask::TitleIsEmpty);
+    Ok(())
+}
+
+// Private descriptions are client-side encrypted bytes sent as base64, so only the
+// encoding is checked
+fn validate_description(description: &str, is_private: bool, config: &Config) -> Result<()> {
+    require!(
+        description.len() <= Config::DESCRIPTION_BYTE_CAP,
+        ErrorTask::DescriptionTooManyBytes
+    );
+    require!(
+        description.chars().count() <= config.max_description_len as usize,
+        ErrorTask::DescriptionTooManyChars
+    );
+    if is_private {
+        require!(
+            !description.is_empty()
+                && description.len() % 4 == 0
+                && description
+                    .trim_end_matches('=')
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'+' || b == b'/'),
+            ErrorTask::InvalidCiphertext
+        );
+        return Ok(());
+    }
+    require!(
+        !description.trim().is_empty(),
+        ErrorTask::DescriptionIsEmpty
+    );
+    Ok(())
+}
+
+fn validate_content_uri(content_uri: &str) -> Result<()> {
+    require!(content_uri.len() <= 200, ErrorTask::UriTooLong);
+    require!(!content_uri.trim().is_empty(), ErrorTask::UriIsEmpty);
+    Ok(())
+}
+
+// Free profiles get `max_tasks_per_user`, an active subscription its tier's limit
+fn require_open_slot(
+    profile: &UserProfile,
+    config: &Config,
+    subscription: Option<&Account<Subscription>>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let limit = match subscription {
+        Some(subscription) if subscription.expires_at > now => {
+            config.tier_task_limits[subscription.tier as usize]
+        }
+        _ => config.max_tasks_per_user,
+    };
+    require!(
+        limit == 0 || profile.open_count < limit,
+        ErrorTask::TooManyOpenTasks
+    );
+    Ok(())
+}
+
+fn validate_comment(body: &str) -> Result<()> {
+    require!(body.len() <= 500, ErrorTask::CommentTooLong);
+    require!(!body.trim().is_empty(), ErrorTask::CommentIsEmpty);
+    Ok(())
+}
+
+fn validate_due_date(due_date: Option<i64>, now: i64) -> Result<()> {
+    if let Some(due) = due_date {
+        require!(due > now, ErrorTask::DueDateInPast);
+    }
+    Ok(())
+}
+
+// Kept within i64 so the variance against the time spent can't overflow
+fn validate_estimate(estimate_seconds: Option<u64>) -> Result<()> {
+    if let Some(estimate) = estimate_seconds {
+        require!(estimate <= i64::MAX as u64, ErrorTask::EstimateTooLarge);
+    }
+    Ok(())
+}
+
+fn validate_limits(max_title_len: u32, max_description_len: u32) -> Result<()> {
+    require!(
+        max_title_len > 0 && max_title_len <= Config::TITLE_LEN_CAP,
+        ErrorTask::InvalidLimit
+    );
+    require!(
+        max_description_len > 0 && max_description_len <= Config::DESCRIPTION_LEN_CAP,
+        ErrorTask::InvalidLimit
+    );
+    Ok(())
+}
+
+fn collect_creation_fee<'info>(
+    config: &Config,
+    payer: &Signer<'info>,
+    treasury: &SystemAccount<'info>,
+    system: &Program<'info, System>,
+) -> Result<()> {
+    if config.creation_fee == 0 {
+        return Ok(());
+    }
+    system_program::transfer(
+        CpiContext::new(
+            system.to_account_info(),
+            system_program::Transfer {
+                from: payer.to_account_info(),
+                to: treasury.to_account_info(),
+            },
+        ),
+        config.creation_fee,
+    )
+}
+
+// The event authority of an `#[event_cpi]` context, so helpers outside a handler can record
+// events the way `emit_cpi!` does
+struct EventAuthority<'info> {
+    info: AccountInfo<'info>,
+    bump: u8,
+}
+
+impl<'info> EventAuthority<'info> {
+    fn emit<E: Event>(&self, event: E) -> Result<()> {
+        let data: Vec<u8> = anchor_lang::event::EVENT_IX_TAG_LE
+            .iter()
+            .copied()
+            .chain(event.data())
+            .collect();
+        let ix = anchor_lang::solana_program::instruction::Instruction::new_with_bytes(
+            crate::ID,
+            &data,
+            vec![AccountMeta::new_readonly(self.info.key(), true)],
+        );
+        anchor_lang::solana_program::program::invoke_signed(
+            &ix,
+            &[self.info.clone()],
+            &[&[b"__event_authority", &[self.bump]]],
+        )?;
+        Ok(())
+    }
+}
+
+fn emit_streak(events: &EventAuthority, profile: &UserProfile, change: StreakChange) -> Result<()> {
+    match change {
+        StreakChange::Unchanged => Ok(()),
+        StreakChange::Extended => events.emit(StreakExtended {
+            author: profile.author,
+            current_streak: profile.current_streak,
+            longest_streak: profile.longest_streak,
+        }),
+        StreakChange::Broken(previous_streak) => events.emit(StreakBroken {
+            author: profile.author,
+            previous_streak,
+            longest_streak: profile.longest_streak,
+        }),
+    }
+}
+
+fn award_xp(events: &EventAuthority, profile: &mut UserProfile, amount: u64) -> Result<()> {
+    profile.xp = profile.xp.saturating_add(amount);
+    let level = UserProfile::level_for(profile.xp);
+    if level > profile.level {
+        profile.level = level;
+        events.emit(LevelUp {
+            author: profile.author,
+            level,
+            xp: profile.xp,
+        })?;
+    }
+    Ok(())
+}
+
+fn emit_milestone_progress(
+    events: &EventAuthority,
+    milestone: &Account<Milestone>,
+    task: &Account<Task>,
+) -> Result<()> {
+    events.emit(MilestoneProgress {
+        milestone: milestone.key(),
+        task: task.key(),
+        task_id: task.task_id,
+        completed: milestone.completed,
+        total: milestone.total,
+    })
+}
+
+// Board tasks must pass their board's log, personal tasks leave no trail
+fn log_activity(
+    log: Option<&mut Account<ActivityLog>>,
+    board: Option<Pubkey>,
+    actor: Pubkey,
+    action: Activity,
+    target: Pubkey,
+) -> Result<()> {
+    let Some(board) = board else {
+        return Ok(());
+    };
+    let log = log.ok_or(ErrorTask::ActivityLogMismatch)?;
+    require_keys_eq!(log.board, board, ErrorTask::ActivityLogMismatch);
+    log.record(actor, action, target, Clock::get()?.slot);
+    Ok(())
+}
+
+// Marks the task done and settles everything hanging off a completion, shared by
+// `complete_task` and `approve_completion`
+// Completion stats, XP and the streak only count once per task, a reopened task just
+// gives its open slot back when it's done again
+fn award_completion(
+    events: &EventAuthority,
+    task: &mut Account<Task>,
+    profile: &mut Account<UserProfile>,
+    global_stats: &mut Account<GlobalStats>,
+    now: i64,
+) -> Result<()> {
+    if task.rewarded {
+        profile.record_recompleted();
+        return Ok(());
+    }
+    task.rewarded = true;
+    let streak = profile.record_completed(now);
+    emit_streak(events, profile, streak)?;
+    award_xp(events, profile, task.priority.xp())?;
+    global_stats.record_completed();
+    Ok(())
+}
+
+// Every dependency and every subtask passed in remaining_accounts must already be done,
+// and all dependencies have to be passed
+fn require_dependencies_done<'info>(
+    task: &Account<Task>,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    let parent_key = task.key();
+    for account_info in remaining_accounts.iter() {
+        let other: Account<Task> = Account::try_from(account_info)?;
+        if task.blocked_by.contains(account_info.key) {
+            require!(other.is_completed(), ErrorTask::DependencyNotCompleted);
+        } else {
+            require!(other.parent == Some(parent_key), ErrorTask::InvalidSubtask);
+            require!(other.is_completed(), ErrorTask::SubtaskNotCompleted);
+        }
+    }
+    require!(
+        task.blocked_by
+            .iter()
+            .all(|dependency| remaining_accounts.iter().any(|a| a.key == dependency)),
+        ErrorTask::MissingDependency
+    );
+    Ok(())
+}
+
+// Adds or takes `task` off the `dependent_count` of its parent and `blocked_by` tasks. Every
+// one of them has to be in `remaining_accounts`, a closed one is passed as is and skipped
+fn count_against_dependencies<'info>(
+    task: &Task,
+    remaining_accounts: &'info [AccountInfo<'info>],
+    add: bool,
+) -> Result<()> {
+    for target in task.parent.iter().chain(task.blocked_by.iter()) {
+        let account_info = remaining_accounts
+            .iter()
+            .find(|a| a.key == target)
+            .ok_or(ErrorTask::MissingDependency)?;
+        if account_info.owner != &crate::ID || account_info.data_is_empty() {
+            continue;
+        }
+        require!(account_info.is_writable, ErrorTask::AccountNotWritable);
+        let mut dependency: Account<Task> = Account::try_from(account_info)?;
+        require!(
+            (1..=Task::VERSION).contains(&dependency.version),
+            ErrorTask::DependencyNotMigrated
+        );
+        dependency.dependent_count = if add {
+            dependency.dependent_count.saturating_add(1)
+        } else {
+            dependency.dependent_count.saturating_sub(1)
+        };
+        dependency.exit(&crate::ID)?;
+    }
+    Ok(())
+}
+
+fn finish_completion(
+    events: &EventAuthority,
+    task: &mut Account<Task>,
+    profile: &mut Account<UserProfile>,
+    global_stats: &mut Account<GlobalStats>,
+    milestone: Option<&mut Account<Milestone>>,
+    stake: Option<&Account<Stake>>,
+    author: Option<&UncheckedAccount>,
+    reward_pool: Option<&mut Account<RewardPool>>,
+    reward_tracker: Option<&mut Account<RewardTracker>>,
+    now: i64,
+) -> Result<()> {
+    task.status = TaskStatus::Done;
+    task.completed_at = Some(now);
+    task.updated_at = now;
+    task.progress = 100;
+    if task.stop_timer(now).is_some() {
+        profile.active_timer = None;
+    }
+    emit_estimate_variance(events, task)?;
+    emit_watched_status(events, task)?;
+    if task.milestone.is_some() {
+        let milestone = milestone.ok_or(ErrorTask::MilestoneMismatch)?;
+        milestone.completed = milestone.completed.saturating_add(1);
+        emit_milestone_progress(events, milestone, task)?;
+    }
+    award_completion(events, task, profile, global_stats, now)?;
+    if let Some(reward_pool) = reward_pool {
+        let tracker = reward_tracker.ok_or(ErrorTask::RewardTrackerMismatch)?;
+        if let Some(amount) = pay_reward(reward_pool, tracker, task, now)? {
+            events.emit(RewardPaid {
+                pool: reward_pool.key(),
+                task: task.key(),
+                task_id: task.task_id,
+                worker: tracker.user,
+                amount,
+            })?;
+        }
+    }
+    // A stake is only handed back when the task is done before its deadline
+    if task.has_stake {
+        let stake = stake.ok_or(ErrorTask::StakeMismatch)?;
+        let author = author.ok_or(ErrorTask::StakeMismatch)?;
+        require_keys_eq!(author.key(), task.author, ErrorTask::StakeMismatch);
+        if now <= stake.deadline {
+            stake.close(author.to_account_info())?;
+            task.has_stake = false;
+        }
+    }
+    Ok(())
+}
+
+// The seeds already tie the delegate to the task's authority and the signer
+fn require_delegate(delegate: Option<&Account<Delegate>>, action: u8) -> Result<()> {
+    let delegate = delegate.ok_or(ErrorTask::Unauthorized)?;
+    require!(
+        delegate.actions & action != 0,
+        ErrorTask::DelegateNotAllowed
+    );
+    require!(
+        Clock::get()?.unix_timestamp < delegate.expires_at,
+        ErrorTask::DelegateExpired
+    );
+    Ok(())
+}
+
+// Fee a Token-2022 transfer-fee mint charges on `amount` this epoch, 0 for classic SPL mints
+fn transfer_fee(mint: &InterfaceAccount<token_interface::Mint>, amount: u64) -> Result<u64> {
+    let info = mint.to_account_info();
+    if *info.owner != spl_token_2022::ID {
+        return Ok(0);
+    }
+    let data = info.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    match state.get_extension::<TransferFeeConfig>() {
+        Ok(config) => Ok(config
+            .calculate_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(ErrorTask::InvalidAmount)?),
+        Err(_) => Ok(0),
+    }
+}
+
+fn charge_subscription<'info>(
+    config: &Config,
+    tier: Tier,
+    periods: u16,
+    author: &Signer<'info>,
+    treasury: &SystemAccount<'info>,
+    system: &Program<'info, System>,
+) -> Result<()> {
+    require!(periods > 0, ErrorTask::InvalidAmount);
+    let price = config.tier_prices[tier as usize];
+    require!(price > 0, ErrorTask::TierNotForSale);
+    let amount = price
+        .checked_mul(periods as u64)
+        .ok_or(ErrorTask::InvalidAmount)?;
+    system_program::transfer(
+        CpiContext::new(
+            system.to_account_info(),
+            system_program::Transfer {
+                from: author.to_account_info(),
+                to: treasury.to_account_info(),
+            },
+        ),
+        amount,
+    )
+}
+
+fn notify(
+    events: &EventAuthority,
+    recipient: Pubkey,
+    kind: NotificationKind,
+    task: &Account<Task>,
+) -> Result<()> {
+    events.emit(Notification {
+        recipient,
+        kind,
+        task: task.key(),
+        task_id: task.task_id,
+    })
+}
+
+// Lamports worth `usd_amount` micro-USD at a fresh, tight enough Pyth SOL/USD price
+fn usd_to_lamports(price_update: &PriceUpdateV2, usd_amount: u64) -> Result<u64> {
+    let feed_id = get_feed_id_from_hex(Escrow::SOL_USD_FEED_ID)?;
+    let price =
+        price_update.get_price_no_older_than(&Clock::get()?, Escrow::MAX_PRICE_AGE, &feed_id)?;
+    require!(
+        price.price > 0 && price.exponent <= 0,
+        ErrorTask::PriceUnavailable
+    );
+    let value = price.price as u128;
+    require!(
+        (price.conf as u128) * 10_000 <= value * Escrow::MAX_CONFIDENCE_BPS as u128,
+        ErrorTask::PriceTooUncertain
+    );
+    // usd / 1e6 dollars at value * 10^exponent dollars per SOL, times 1e9 lamports per SOL
+    let scale = 10u128
+        .checked_pow(price.exponent.unsigned_abs())
+        .ok_or(ErrorTask::PriceUnavailable)?;
+    let lamports = (usd_amount as u128)
+        .checked_mul(1_000)
+        .and_then(|v| v.checked_mul(scale))
+        .ok_or(ErrorTask::InvalidAmount)?
+        / value;
+    u64::try_from(lamports).map_err(|_| error!(ErrorTask::InvalidAmount))
+}
+
+// Positive variance means the task took longer than estimated
+fn emit_estimate_variance(events: &EventAuthority, task: &Account<Task>) -> Result<()> {
+    if let Some(estimate_seconds) = task.estimate_seconds {
+        events.emit(EstimateVariance {
+            task: task.key(),
+            task_id: task.task_id,
+            author: task.author,
+            estimate_seconds,
+            tracked_seconds: task.total_seconds_spent,
+            variance_seconds: (task.total_seconds_spent as i128 - estimate_seconds as i128)
+                .clamp(i64::MIN as i128, i64::MAX as i128) as i64,
+        })?;
+    }
+    Ok(())
+}
+
+// Only emitted while someone watches the task, no point paying for the log otherwise
+fn emit_watched_status(events: &EventAuthority, task: &Account<Task>) -> Result<()> {
+    if task.watcher_count > 0 {
+        events.emit(WatchedStatusChanged {
+            task: task.key(),
+            task_id: task.task_id,
+            status: task.status,
+            watcher_count: task.watcher_count,
+        })?;
+    }
+    Ok(())
+}
+
+fn replace_compressed_task<'info>(
+    ctx: &Context<'_, '_, 'info, 'info, ModifyTaskTree<'info>>,
+    root: [u8; 32],
+    previous: &CompressedTask,
+    task: CompressedTask,
+) -> Result<()> {
+    let task_tree = &ctx.accounts.task_tree;
+    require_keys_eq!(previous.author, task_tree.author, ErrorTask::Unauthorized);
+    let leaf = task.leaf()?;
+
+    let merkle_tree = task_tree.merkle_tree;
+    let bump = [task_tree.bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[b"task_tree", merkle_tree.as_ref(), &bump]];
+    spl_account_compression::cpi::replace_leaf(
+        CpiContext::new_with_signer(
+            ctx.accounts.compression_program.to_account_info(),
+            ModifyTree {
+                merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+                authority: task_tree.to_account_info(),
+                noop: ctx.accounts.log_wrapper.to_account_info(),
+            },
+            signer_seeds,
+        )
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+        root,
+        previous.leaf()?,
+        leaf,
+        previous.index,
+    )?;
+
+    emit_cpi!(CompressedTaskChanged {
+        merkle_tree,
+        leaf,
+        task,
+    });
+    Ok(())
+}
+
+// The worker is the accepted assignee, or the author when nobody took the task. A drained
+// pool, a used up daily cap or a task that was already paid pays nothing, the completion
+// still goes through
+fn pay_reward(
+    pool: &mut Account<RewardPool>,
+    tracker: &mut Account<RewardTracker>,
+    task: &mut Account<Task>,
+    now: i64,
+) -> Result<Option<u64>> {
+    require!(
+        task.board == Some(pool.board),
+        ErrorTask::RewardPoolMismatch
+    );
+    if task.reward_paid {
+        return Ok(None);
+    }
+    let worker = match task.assignee {
+        Some(assignee) if task.assignment_accepted => assignee,
+        _ => task.author,
+    };
+    require_keys_eq!(tracker.pool, pool.key(), ErrorTask::RewardTrackerMismatch);
+    require_keys_eq!(tracker.user, worker, ErrorTask::RewardTrackerMismatch);
+
+    let pool_info = pool.to_account_info();
+    let available = pool_info
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(pool_info.data_len()));
+    if !tracker.can_earn(now, pool.daily_cap) || available < pool.reward_per_task {
+        return Ok(None);
+    }
+
+    let amount = pool.reward_per_task;
+    **pool_info.try_borrow_mut_lamports()? -= amount;
+    **tracker.to_account_info().try_borrow_mut_lamports()? += amount;
+    pool.total_paid = pool.total_paid.saturating_add(amount);
+    tracker.rewards_today = tracker.rewards_today.saturating_add(1);
+    tracker.total_earned = tracker.total_earned.saturating_add(amount);
+    task.reward_paid = true;
+    Ok(Some(amount))
+}
+
+// What `init` does for an account created by hand: someone may have sent the address
+// lamports ahead of time, so instead of `create_account` failing the missing rent is topped
+// up before the space is allocated and handed to the program
+fn create_pda<'info>(
+    payer: &Signer<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    space: usize,
+    rent: &Rent,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    let lamports = rent.minimum_balance(space);
+    if account.lamports() == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.to_account_info(),
+                system_program::CreateAccount {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+                &[signer_seeds],
+            ),
+            lamports,
+            space as u64,
+            &crate::ID,
+        );
+    }
+    let missing = lamports.saturating_sub(account.lamports());
+    if missing > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            missing,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::Allocate {
+                account_to_allocate: account.clone(),
+            },
+            &[signer_seeds],
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::Assign {
+                account_to_assign: account.clone(),
+            },
+            &[signer_seeds],
+        ),
+        &crate::ID,
+    )
+}
+
+// Profiles from before `init_task_index` create tasks without an index. A full index is
+// reallocated with room for `TaskIndex::GROWTH` more ids, `payer` covers the extra rent
+fn index_task<'info>(
+    task_index: Option<&mut Account<'info, TaskIndex>>,
+    payer: &Signer<'info>,
+    system: &Program<'info, System>,
+    task_id: u64,
+) -> Result<()> {
+    let Some(task_index) = task_index else {
+        return Ok(());
+    };
+    let info = task_index.to_account_info();
+    let needed = 8 + 32 + 4 + (task_index.task_ids.len() + 1) * 8;
+    if needed > info.data_len() {
+        let new_len = info.data_len() + TaskIndex::GROWTH * 8;
+        let missing = Rent::get()?
+            .minimum_balance(new_len)
+            .saturating_sub(info.lamports());
+        if missing > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    system.to_account_info(),
+                    system_program::Transfer {
+                        from: payer.to_account_info(),
+                        to: info.clone(),
+                    },
+                ),
+                missing,
+            )?;
+        }
+        info.realloc(new_len, false)?;
+    }
+    task_index.task_ids.push(task_id);
+    Ok(())
+}
+
+#[event]
+pub struct TaskReopened {
+    pub task: Pubkey,
+    pub author: Pubkey,
+    pub reopened_count: u32,
+    pub timestamp: i64,
+    pub task_id: u64,
+}
+
+#[event]
+pub struct TaskCreated {
+    pub task: Pubkey,
+    pub author: Pubkey,
+    pub created_at: i64,
+    pub task_id: u64,
+}
+
+#[event]
+pub struct TaskUpdated {
+    pub task: Pubkey,
+    pub author: Pubkey,
+    pub updated_at: i64,
+    pub task_id: u64,
+}
+
+#[event]
+pub struct TaskCompleted {
+    pub task: Pubkey,
+    pub author: Pubkey,
+    pub completed_by: Pubkey,
+    pub completed_at: i64,
+    pub task_id: u64,
+}
+
+#[event]
+pub struct TaskDeleted {
+    pub task: Pubkey,
+    pub author: Pubkey,
+    pub deleted_at: i64,
+    pub task_id: u64,
+}
+
+#[event]
+pub struct TaskTransferred {
+    pub old_task: Pubkey,
+    pub task: Pubkey,
+    pub previous_author: Pubkey,
+    pub author: Pubkey,
+    pub timestamp: i64,
+    pub task_id: u64,
+}
+
+#[event]
+pub struct TaskDueSoon {
+    pub task: Pubkey,
+    pub author: Pubkey,
+    pub due_date: i64,
+    pub timestamp: i64,
+    pub task_id: u64,
+}
+
+#[event]
+pub struct TaskOverdue {
+    pub task: Pubkey,
+    pub author: Pubkey,
+    pub due_date: i64,
+    pub timestamp: i64,
+    pub task_id: u64,
+}
+
+#[event]
+pub struct StreakExtended {
+    pub author: Pubkey,
+    pub current_streak: u32,
+    pub longest_streak: u32,
+}
+
+#[event]
+pub struct StreakBroken {
+    pub author: Pubkey,
+    pub previous_streak: u32,
+    pub longest_streak: u32,
+}
+
+#[event]
+pub struct LevelUp {
+    pub author: Pubkey,
+    pub level: u8,
+    pub xp: u64,
+}
+
+#[event]
+pub struct MilestoneProgress {
+    pub milestone: Pubkey,
+    pub task: Pubkey,
+    pub completed: u64,
+    pub total: u64,
+    pub task_id: u64,
+}
+
+#[event]
+pub struct TaskSubmittedForReview {
+    pub task: Pubkey,
+    pub submitted_by: Pubkey,
+    pub reviewer: Pubkey,
+    pub timestamp: i64,
+    pub task_id: u64,
+}
+
+#[event]
+pub struct ChangesRequested {
+    pub task: Pubkey,
+    pub reviewer: Pubkey,
+    pub timestamp: i64,
+    pub task_id: u64,
+}
+
+#[event]
+pub struct TaskApproved {
+    pub task: Pubkey,
+    pub approver: Pubkey,
+    pub approvals: u8,
+    pub required: u8,
+    pub task_id: u64,
+}
+
+#[event]
+pub struct TaskAuthorityChanged {
+    pub task: Pubkey,
+    pub previous: Pubkey,
+    pub authority: Pubkey,
+    pub task_id: u64,
+}
+
+#[event]
+pub struct TaskTipped {
+    pub task: Pubkey,
+    pub author: Pubkey,
+    pub tipper: Pubkey,
+    // `None` for a lamport tip
+    pub mint: Option<Pubkey>,
+    pub amount: u64,
+    pub message: String,
+    pub task_id: u64,
+}
+
+// One event for everything a user should hear about, so notification services can filter
+// on `recipient` alone. `NotificationKind` is append-only to keep its encoding stable
+#[event]
+pub struct Notification {
+    pub recipient: Pubkey,
+    pub kind: NotificationKind,
+    pub task: Pubkey,
+    pub task_id: u64,
+}
+
+#[event]
+pub struct EstimateVariance {
+    pub task: Pubkey,
+    pub author: Pubkey,
+    pub estimate_seconds: u64,
+    pub tracked_seconds: u64,
+    pub variance_seconds: i64,
+    pub task_id: u64,
+}
+
+#[event]
+pub struct CommentMention {
+    pub task: Pubkey,
+    pub comment: Pubkey,
+    pub author: Pubkey,
+    pub mentioned: Pubkey,
+    pub task_id: u64,
+}
+
+#[event]
+pub struct TaskReacted {
+    pub task: Pubkey,
+    pub reactor: Pubkey,
+    pub emoji_code: u32,
+    pub comment_id: Option<u64>,
+    pub task_id: u64,
+}
+
+#[event]
+pub struct WatchedStatusChanged {
+    pub task: Pubkey,
+    pub status: TaskStatus,
+    pub watcher_count: u32,
+    pub task_id: u64,
+}
+
+#[event]
+pub struct MemberInvited {
+    pub board: Pubkey,
+    pub member: Pubkey,
+    pub role: Role,
+    pub inviter: Pubkey,
+}
+
+#[event]
+pub struct CompressedTaskChanged {
+    pub merkle_tree: Pubkey,
+    pub leaf: [u8; 32],
+    pub task: CompressedTask,
+}
+
+#[event]
+pub struct RewardPaid {
+    pub pool: Pubkey,
+    pub task: Pubkey,
+    pub task_id: u64,
+    pub worker: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct DisputeRaised {
+    pub task: Pubkey,
+    pub task_id: u64,
+    pub raised_by: Pubkey,
+    pub reason: String,
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub task: Pubkey,
+    pub task_id: u64,
+    pub arbiter: Pubkey,
+    pub worker_amount: u64,
+    pub funder_amount: u64,
 }
 
 #[error_code]
 pub enum ErrorTask {
-    #[msg("Title can't be more then 100 chars")]
-    TitleTooLong,
-    #[msg("Description can't be more then 1000 chars")]
-    DescriptionTooLong,
+    #[msg("Title has more characters then the configured limit")]
+    TitleTooManyChars,
+    #[msg("Description has more characters then the configured limit")]
+    DescriptionTooManyChars,
     #[msg("Title is empty")]
     TitleIsEmpty,
     #[msg("Description is empty")]
@@ -163,4 +11046,348 @@ pub enum ErrorTask {
     Unauthorized,
     #[msg("Title not found")]
     TitleNotFound,
+    #[msg("Task count overflow")]
+    TaskCountOverflow,
+    #[msg("Due date is in the past")]
+    DueDateInPast,
+    #[msg("Task can't move to that status")]
+    InvalidStatusTransition,
+    #[msg("Task is not completed")]
+    TaskNotCompleted,
+    #[msg("Tag can't be more then 20 chars")]
+    TagTooLong,
+    #[msg("Tag is empty")]
+    TagIsEmpty,
+    #[msg("Task can't have more then 5 tags")]
+    TooManyTags,
+    #[msg("Tag already exists on the task")]
+    DuplicateTag,
+    #[msg("Tag not found")]
+    TagNotFound,
+    #[msg("Account is not a subtask of this task")]
+    InvalidSubtask,
+    #[msg("Subtask is not completed")]
+    SubtaskNotCompleted,
+    #[msg("Task can't depend on itself")]
+    SelfDependency,
+    #[msg("Task can't have more then 10 dependencies")]
+    TooManyDependencies,
+    #[msg("Dependency already exists on the task")]
+    DuplicateDependency,
+    #[msg("Dependency not found")]
+    DependencyNotFound,
+    #[msg("Dependency is not completed")]
+    DependencyNotCompleted,
+    #[msg("Not all dependencies were passed")]
+    MissingDependency,
+    #[msg("Board name can't be more then 50 chars")]
+    BoardNameTooLong,
+    #[msg("Board name is empty")]
+    BoardNameIsEmpty,
+    #[msg("Board count overflow")]
+    BoardCountOverflow,
+    #[msg("Board still has tasks")]
+    BoardNotEmpty,
+    #[msg("Board does not match the task")]
+    BoardMismatch,
+    #[msg("Signer is not the assignee")]
+    NotAssignee,
+    #[msg("Author is not an editor on the board")]
+    NotBoardEditor,
+    #[msg("Member is already on the board")]
+    DuplicateMember,
+    #[msg("Board can't have more then 10 members")]
+    TooManyMembers,
+    #[msg("Member not found")]
+    MemberNotFound,
+    #[msg("Comment can't be more then 500 chars")]
+    CommentTooLong,
+    #[msg("Comment is empty")]
+    CommentIsEmpty,
+    #[msg("Comment count overflow")]
+    CommentCountOverflow,
+    #[msg("Amount must be more then zero")]
+    InvalidAmount,
+    #[msg("Task is already completed")]
+    TaskAlreadyCompleted,
+    #[msg("Completed task still has an unclaimed bounty")]
+    BountyNotClaimed,
+    #[msg("Escrow does not match the task")]
+    EscrowMismatch,
+    #[msg("Task still has an open token bounty")]
+    TokenBountyOpen,
+    #[msg("Stake does not match the task")]
+    StakeMismatch,
+    #[msg("Task still has a locked stake")]
+    StakeLocked,
+    #[msg("Task needs a due date")]
+    DueDateRequired,
+    #[msg("Deadline hasn't passed yet")]
+    DeadlineNotPassed,
+    #[msg("Use complete_task to finish a task")]
+    UseCompleteTask,
+    #[msg("Offset is past the end of the description")]
+    InvalidOffset,
+    #[msg("Task is already archived")]
+    AlreadyArchived,
+    #[msg("Task is not archived")]
+    NotArchived,
+    #[msg("Only finished tasks can be archived")]
+    TaskStillOpen,
+    #[msg("Account is not writable")]
+    AccountNotWritable,
+    #[msg("No transfer is pending for this wallet")]
+    NoPendingTransfer,
+    #[msg("Task still holds a bounty or stake")]
+    TaskHasFunds,
+    #[msg("Task can't be transferred to its own author")]
+    TransferToSelf,
+    #[msg("Limit is outside the allowed range")]
+    InvalidLimit,
+    #[msg("Program is paused")]
+    ProgramPaused,
+    #[msg("Interval must be more then zero")]
+    InvalidInterval,
+    #[msg("Recurring task count overflow")]
+    RecurringCountOverflow,
+    #[msg("Next recurrence isn't due yet")]
+    RecurrenceNotDue,
+    #[msg("Deposit can't cover the rent of another task")]
+    InsufficientDeposit,
+    #[msg("Task is not open")]
+    TaskNotOpen,
+    #[msg("Uri can't be more then 200 chars")]
+    UriTooLong,
+    #[msg("Badge was already minted for this task")]
+    BadgeAlreadyMinted,
+    #[msg("Task does not qualify for a badge")]
+    BadgeNotEarned,
+    #[msg("Merkle tree isn't the configured badge tree")]
+    BadgeTreeMismatch,
+    #[msg("Achievement name can't be more then 32 chars")]
+    AchievementNameTooLong,
+    #[msg("Profile doesn't meet the achievement criteria")]
+    AchievementNotMet,
+    #[msg("Task doesn't have an accepted assignee")]
+    AssignmentNotAccepted,
+    #[msg("Work on this task was already accepted")]
+    ReputationAlreadyRecorded,
+    #[msg("Checklist item can't be more then 50 chars")]
+    ItemTooLong,
+    #[msg("Checklist item can't be empty")]
+    ItemIsEmpty,
+    #[msg("Task can't have more then 10 checklist items")]
+    TooManyItems,
+    #[msg("Checklist item not found")]
+    ItemNotFound,
+    #[msg("Progress can't be more then 100")]
+    InvalidProgress,
+    #[msg("Progress can't be lowered on a completed task")]
+    ProgressLowered,
+    #[msg("Uri can't be empty")]
+    UriIsEmpty,
+    #[msg("Task can't have more then 5 attachments")]
+    TooManyAttachments,
+    #[msg("Attachment is already linked to the task")]
+    DuplicateAttachment,
+    #[msg("Attachment not found")]
+    AttachmentNotFound,
+    #[msg("Private description must be non empty base64")]
+    InvalidCiphertext,
+    #[msg("Task content is kept off-chain, use set_content")]
+    ContentIsHashed,
+    #[msg("Task content isn't kept off-chain")]
+    ContentNotHashed,
+    #[msg("Title is too many bytes to store")]
+    TitleTooManyBytes,
+    #[msg("Description is too many bytes to store")]
+    DescriptionTooManyBytes,
+    #[msg("Task is already completed")]
+    AlreadyCompleted,
+    #[msg("Profile has reached the configured open task limit")]
+    TooManyOpenTasks,
+    #[msg("Task index is full, delete some tasks first")]
+    TaskIndexFull,
+    #[msg("Task is already pinned")]
+    AlreadyPinned,
+    #[msg("Task isn't pinned")]
+    NotPinned,
+    #[msg("Can't pin more then 10 tasks")]
+    TooManyPinned,
+    #[msg("Task has been snoozed the maximum number of times")]
+    TooManySnoozes,
+    #[msg("Milestone name can't be more then 50 chars")]
+    MilestoneNameTooLong,
+    #[msg("Milestone name can't be empty")]
+    MilestoneNameIsEmpty,
+    #[msg("Milestone doesn't match the task's milestone")]
+    MilestoneMismatch,
+    #[msg("Task is linked to a milestone, unlink it first")]
+    MilestoneLinked,
+    #[msg("Sprint name can't be more then 50 chars")]
+    SprintNameTooLong,
+    #[msg("Sprint name can't be empty")]
+    SprintNameIsEmpty,
+    #[msg("Sprint has to end after it starts")]
+    InvalidSprintWindow,
+    #[msg("Sprint isn't open")]
+    SprintNotOpen,
+    #[msg("Sprint doesn't match the task's sprint")]
+    SprintMismatch,
+    #[msg("Task is in a sprint, leave it first")]
+    SprintLinked,
+    #[msg("Every task in the sprint has to be passed exactly once")]
+    SprintTasksMissing,
+    #[msg("Label name can't be more then 32 chars")]
+    LabelNameTooLong,
+    #[msg("Label name can't be empty")]
+    LabelNameIsEmpty,
+    #[msg("Label is still applied to tasks")]
+    LabelInUse,
+    #[msg("Task can't have more then 5 labels")]
+    TooManyLabels,
+    #[msg("Label is already applied to the task")]
+    DuplicateLabel,
+    #[msg("Label not found on the task")]
+    LabelNotFound,
+    #[msg("Task has labels applied, remove them first")]
+    LabelsApplied,
+    #[msg("Batch needs 1 to 10 items, one task account per item")]
+    InvalidBatchSize,
+    #[msg("Task account isn't the PDA for the next task id")]
+    InvalidTaskAccount,
+    #[msg("Task is already in the trash")]
+    AlreadyTrashed,
+    #[msg("Task isn't in the trash")]
+    NotTrashed,
+    #[msg("Task can't be purged before the trash grace window is over")]
+    TrashGraceNotOver,
+    #[msg("Activity log is missing or belongs to another board")]
+    ActivityLogMismatch,
+    #[msg("Task is waiting on its reviewer")]
+    AwaitingReview,
+    #[msg("Task is not pending review")]
+    NotPendingReview,
+    #[msg("Only the task reviewer can do this")]
+    NotReviewer,
+    #[msg("Task needs a reviewer sign-off and must go through complete_task")]
+    ReviewRequired,
+    #[msg("Review note can not be more then 200 characters")]
+    ReviewNoteTooLong,
+    #[msg("A task can't have both a reviewer and required approvals")]
+    ReviewerAndApprovals,
+    #[msg("Task completes through approve_task once enough members approve")]
+    ApprovalsRequired,
+    #[msg("Task does not require approvals")]
+    ApprovalsNotRequired,
+    #[msg("Required approvals can not be more then the board has members")]
+    TooManyApprovals,
+    #[msg("Signer is not a member of the board")]
+    NotBoardMember,
+    #[msg("Member has already approved this task")]
+    AlreadyApproved,
+    #[msg("Session key has expired")]
+    DelegateExpired,
+    #[msg("Session key is not allowed to call this instruction")]
+    DelegateNotAllowed,
+    #[msg("Delegate actions must be a non-empty mask of known instructions")]
+    InvalidDelegateActions,
+    #[msg("Mint is not accepted for bounties")]
+    MintNotAllowed,
+    #[msg("Allowed mints can not be more then 8")]
+    TooManyAllowedMints,
+    #[msg("Tip message can not be more then 200 characters")]
+    TipMessageTooLong,
+    #[msg("Token tips need the mint, both token accounts and the token program, lamport tips none of them")]
+    TipAccountMismatch,
+    #[msg("Subscription tier is not for sale")]
+    TierNotForSale,
+    #[msg("Subscription tier can only change once it has lapsed")]
+    SubscriptionActive,
+    #[msg("Oracle account is missing or is not the stake's feed")]
+    OracleMismatch,
+    #[msg("A fresh Pyth SOL/USD price is required")]
+    PriceUnavailable,
+    #[msg("Price confidence interval is too wide")]
+    PriceTooUncertain,
+    #[msg("A timer is already running, stop it first")]
+    TimerRunning,
+    #[msg("No timer is running on this task")]
+    TimerNotRunning,
+    #[msg("Work log note can not be more then 200 characters")]
+    WorkNoteTooLong,
+    #[msg("Metadata key can not be more then 32 characters")]
+    MetadataKeyTooLong,
+    #[msg("Metadata key can not be empty")]
+    MetadataKeyIsEmpty,
+    #[msg("Metadata value can not be more then 64 characters")]
+    MetadataValueTooLong,
+    #[msg("A task can not have more then 5 metadata entries")]
+    TooManyMetadataEntries,
+    #[msg("Metadata entry not found")]
+    MetadataNotFound,
+    #[msg("A comment can not mention more then 5 wallets")]
+    TooManyMentions,
+    #[msg("Wallet is mentioned twice")]
+    DuplicateMention,
+    #[msg("Mentioned wallet is not part of the task")]
+    InvalidMention,
+    #[msg("Wallet already reacted with this emoji")]
+    AlreadyReacted,
+    #[msg("Task can not have more then 32 reactions")]
+    TooManyReactions,
+    #[msg("Reaction not found")]
+    ReactionNotFound,
+    #[msg("Comment does not belong to this task")]
+    CommentMismatch,
+    #[msg("Authority can not watch its own task")]
+    CannotWatchOwnTask,
+    #[msg("Task is not open to contributions from this wallet")]
+    TaskNotVisible,
+    #[msg("Each board task needs its author, profile and task index")]
+    BoardTaskAccountsMissing,
+    #[msg("Task tree has no leaves left")]
+    TaskTreeFull,
+    #[msg("Account is not a task")]
+    InvalidTaskAccount,
+    #[msg("Task is already on the current version")]
+    AlreadyMigrated,
+    #[msg("Task id is taken by a different task")]
+    TaskIdTaken,
+    #[msg("Memo can not be more then 200 bytes")]
+    MemoTooLong,
+    #[msg("Memo program account is required for a memo")]
+    MemoProgramMissing,
+    #[msg("Reward pool belongs to a different board")]
+    RewardPoolMismatch,
+    #[msg("Reward tracker does not match the pool and worker")]
+    RewardTrackerMismatch,
+    #[msg("Bounty is under dispute")]
+    BountyDisputed,
+    #[msg("Bounty is not under dispute")]
+    NotDisputed,
+    #[msg("No arbiter is configured")]
+    NoArbiter,
+    #[msg("Only the configured arbiter can resolve disputes")]
+    NotArbiter,
+    #[msg("Dispute reason can not be more then 200 bytes")]
+    DisputeReasonTooLong,
+    #[msg("Worker share can not be more then 10000 bps")]
+    InvalidSplit,
+    #[msg("Bounty can't be claimed by the task's authority or its funder")]
+    SelfClaim,
+    #[msg("Bounty can't be disputed before an assignee has accepted the task")]
+    NoWorker,
+    #[msg("Estimate can not be more then i64::MAX seconds")]
+    EstimateTooLarge,
+    #[msg("Task with comments, subtasks or dependents can't be transferred")]
+    HasDependents,
+    #[msg("Treasury has to stay rent-exempt")]
+    TreasuryNotRentExempt,
+    #[msg("Authority can't accept its own work")]
+    SelfAccept,
+    #[msg("Parent and dependencies have to be migrated first")]
+    DependencyNotMigrated,
+    #[msg("Task with a parent or dependencies has to be closed with `delete_task`")]
+    DependenciesLinked,
 }